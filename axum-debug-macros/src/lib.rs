@@ -20,6 +20,22 @@ use proc_macro::TokenStream;
 
 /// Generates better error messages when applied to a handler function.
 ///
+/// Can also be applied to associated functions inside `impl` blocks and to generic functions. Generic
+/// handlers are checked against their own bounds and `where` clause.
+///
+/// The handler is emitted as written and the checks are placed in a separate item next to it, so
+/// its type and the future it returns don't change. Handlers mentioning `Self` are checked through
+/// `Self`. Other handlers could be free or associated functions, so the checks take a copy of the
+/// handler instead, which is type checked a second time. `#[debug_handler(associated)]` checks an
+/// associated function through `Self` even if it doesn't mention it, avoiding the copy. Other
+/// attributes on the handler, including doc comments and attribute macros like
+/// `#[tracing::instrument]`, are kept. The handler's `cfg` attributes are copied onto the generated
+/// items.
+///
+/// Checks are only done in debug builds of the crate using the macro. To check release builds too,
+/// use `#[debug_handler(always)]` or enable the `always` feature of `axum-debug`. Either way the
+/// checks happen at compile time only.
+///
 /// # Examples
 ///
 /// Function is not async:
@@ -56,6 +72,39 @@ use proc_macro::TokenStream;
 ///    |                       ^^^^
 ///    |                       |
 ///    |                       the trait `IntoResponse` is not implemented for `bool`
+///    |
+///    = note: use `StatusCode` to respond with a status, or `(StatusCode, T)` to respond with a status and a body
+/// ```
+///
+/// Returning `bool`, numbers, `Option`, errors or functions comes with a suggestion like above.
+///
+/// The `Ok` and `Err` types of a `Result` are checked separately, so the error points at the one
+/// that doesn't implement `IntoResponse`:
+///
+/// ```rust,ignore
+/// #[debug_handler]
+/// async fn handler() -> Result<String, MyError> {
+///     Err(MyError)
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `MyError: IntoResponse` is not satisfied
+///   --> main.rs:xx:38
+///    |
+/// xx | async fn handler() -> Result<String, MyError> {
+///    |                                      ^^^^^^^ the trait `IntoResponse` is not implemented for `MyError`
+/// ```
+///
+/// Aliases of `Result` can be registered by name to be checked the same way:
+///
+/// ```rust,ignore
+/// type ApiResult<T> = Result<T, ApiError>;
+///
+/// #[debug_handler(result(ApiResult))]
+/// async fn handler() -> ApiResult<String> {
+///     Ok(String::from("Hello, world"))
+/// }
 /// ```
 ///
 /// Wrong extractor:
@@ -117,6 +166,26 @@ use proc_macro::TokenStream;
 ///    | |______________^
 /// ```
 ///
+/// Handler takes `self`:
+///
+/// ```rust,ignore
+/// impl UserController {
+///     #[debug_handler]
+///     async fn list(self) -> String {
+///         self.users.join(", ")
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// error: handlers cannot take `self`
+/// note: use an associated function without a receiver instead
+///   --> main.rs:xx:19
+///    |
+/// xx |     async fn list(self) -> String {
+///    |                   ^^^^
+/// ```
+///
 /// Future is not [`Send`]:
 ///
 /// ```rust,ignore
@@ -129,27 +198,158 @@ use proc_macro::TokenStream;
 /// ```
 ///
 /// ```text
-/// error: future cannot be sent between threads safely
-///   --> main.rs:xx:10
+/// error[E0277]: `Rc<()>` cannot be sent between threads safely
+///   --> main.rs:xx:9
+///    |
+/// xx |     let not_send = std::rc::Rc::new(());
+///    |         ^^^^^^^^ `Rc<()>` cannot be sent between threads safely
+/// ```
+///
+/// Every `let` binding that is held across an `.await` is checked on its own, so the error points
+/// at the binding that makes the future not [`Send`]. Bindings that might be moved before the
+/// `.await`, like by passing them to a function or a macro or calling a method on them, are left to
+/// the check of the whole future.
+///
+/// The assertions are inserted inside `if false { .. }`, so they are type checked but never run.
+/// They go in the copy of the handler the checks take, leaving the handler's body as written. Only
+/// handlers checked through `Self` have the assertions in their own body.
+///
+/// Body extractor is not the last argument:
+///
+/// ```rust,ignore
+/// #[debug_handler]
+/// async fn handler(body: String, method: Method) {}
+/// ```
+///
+/// ```text
+/// error: `String` consumes the request body and must be the last argument
+///   --> main.rs:xx:24
+///    |
+/// xx | async fn handler(body: String, method: Method) {}
+///    |                        ^^^^^^
+/// ```
+///
+/// More than one body extractor:
+///
+/// ```rust,ignore
+/// #[debug_handler]
+/// async fn handler(json: Json<Value>, body: String) {}
+/// ```
+///
+/// ```text
+/// error: `String` consumes the request body, but `Json` already does
+/// note: only one extractor can consume the request body
+///   --> main.rs:xx:43
+///    |
+/// xx | async fn handler(json: Json<Value>, body: String) {}
+///    |                                           ^^^^^^
+/// ```
+///
+/// `Json`, `Form`, `String`, `Bytes`, `BodyStream`, `Multipart`, `RawBody` and `Request` are known
+/// to consume the request body. Other extractors that do can be registered by name:
+///
+/// ```rust,ignore
+/// #[debug_handler(body(Protobuf))]
+/// async fn handler(message: Protobuf<Message>) {}
+/// ```
+///
+/// # Path captures
+///
+/// With `#[debug_handler(path = "/users/:id")]`, the path is checked like [`debug_path`] does, and
+/// `Path` extractors taking a tuple or a single value must take one value for each capture:
+///
+/// ```rust,ignore
+/// #[debug_handler(path = "/users/:id")]
+/// async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+/// ```
+///
+/// ```text
+/// error: `Path` extracts 2 parameters, but `/users/:id` has 1 capture
+///   --> main.rs:xx:42
 ///    |
-/// xx | async fn handler() {
-///    |          ^^^^^^^
-///    |          |
-///    |          future returned by `handler` is not `Send`
+/// xx | async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+///    |                                      ^^^^^^^^^^^^^^^^
+/// ```
+///
+/// # Explaining rejections
+///
+/// With `#[debug_handler(rejections)]`, a `debug_` wrapper of the handler is generated that answers
+/// a rejected request with the rejection along with which argument rejected it and why. Route the
+/// wrapper instead of the handler, and add `axum_debug::ExplainRejectionsLayer` to the router to
+/// get that information in the response body.
+///
+/// ```rust,ignore
+/// #[debug_handler(rejections)]
+/// async fn create_user(Json(user): Json<User>) -> String {
+///     user.name
+/// }
+///
+/// let app = Router::new().route("/users", post(debug_create_user));
+/// ```
+///
+/// Rejections of the extractors must implement [`Debug`]. The wrapper of a handler with a `Json` or
+/// `Form` argument buffers the request body, to find the field they failed to deserialize. See
+/// [wrapped handlers](#wrapped-handlers) for what the wrapper looks like.
+///
+/// # Tracing
+///
+/// With `#[debug_handler(trace)]`, a `debug_` wrapper of the handler is generated that runs each
+/// extractor in its own [`tracing`] span, named after the argument and recording whether the
+/// extractor rejected the request, followed by the handler in a `body` span. This shows whether a
+/// slow request spends its time in an extractor or in the handler.
+///
+/// ```rust,ignore
+/// #[debug_handler(trace)]
+/// async fn profile(user: CurrentUser, Path(id): Path<u32>) -> String {
+///     load_profile(user, id).await
+/// }
+///
+/// let app = Router::new().route("/profiles/:id", get(debug_profile));
+/// ```
+///
+/// See `axum_debug::trace` for the fields of the spans. `trace` can be combined with `rejections`.
+///
+/// # Wrapped handlers
+///
+/// With `rejections` or `trace`, the handler is left as written, along with its attributes, and a
+/// wrapper named after it with a `debug_` prefix is added next to it, with the same visibility:
+///
+/// ```rust,ignore
+/// async fn debug_create_user(
+///     req: axum::http::Request<axum::body::Body>,
+/// ) -> axum::http::Response<axum::body::BoxBody>
 /// ```
 ///
+/// The wrapper runs the extractors one at a time, stopping at the first rejection like axum does,
+/// and then calls the handler. It is the same in debug and release builds, so routes using it
+/// compile in both.
+///
+/// The handler can't be generic, and it takes `axum::body::Body` requests only. The wrapper of an
+/// associated function is in the same `impl` block, like `Controller::debug_show`, and calls the
+/// handler through `Self`, so functions not mentioning `Self` need `associated` too.
+///
+/// [`tracing`]: https://docs.rs/tracing
 /// [`Send`]: Send
+/// [`Debug`]: std::fmt::Debug
+/// [`debug_path`]: macro@debug_path
 #[proc_macro_attribute]
-pub fn debug_handler(_attr: TokenStream, input: TokenStream) -> TokenStream {
-    #[cfg(not(debug_assertions))]
-    return input;
-
-    #[cfg(debug_assertions)]
-    return debug::apply_debug_handler(input);
+pub fn debug_handler(attr: TokenStream, input: TokenStream) -> TokenStream {
+    debug::apply_debug_handler(attr, input)
 }
 
 /// Shortens error message when applied to a [`Router`].
 ///
+/// Takes any expression evaluating to a [`Router`] and evaluates to the boxed router, so its type is
+/// the same in debug and release builds.
+///
+/// In debug builds, handlers extracting an `Extension` that no `AddExtensionLayer` in the expression
+/// provides to them, and routes shadowed by routes tried before them, are printed as warnings to
+/// stderr once the router is built. Only handlers and
+/// layers written inside the macro are seen.
+///
+/// Setting the `AXUM_DEBUG_ROUTES` environment variable to `text` or `json` also prints the routes of
+/// the router, with the methods and handlers serving them.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -158,9 +358,7 @@ pub fn debug_handler(_attr: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     let app = Router::new().route("/", get(handler));
-///
-///     debug_router!(app);
+///     let app = debug_router!(Router::new().route("/", get(handler)));
 ///
 ///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
 ///         .serve(app.into_make_service())
@@ -176,120 +374,1676 @@ pub fn debug_handler(_attr: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// [`Router`]: axum::routing::Router
 #[proc_macro]
-pub fn debug_router(_input: TokenStream) -> TokenStream {
-    #[cfg(not(debug_assertions))]
-    return TokenStream::new();
+pub fn debug_router(input: TokenStream) -> TokenStream {
+    debug::apply_debug_router(input)
+}
+
+/// Checks the syntax of a route path at compile time.
+///
+/// Evaluates to the path itself, so it can be passed to `Router::route` directly. Paths must start
+/// with `/`, captures must be whole segments like `:id`, and a wildcard like `*rest` can only be
+/// the last segment.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use axum::{handler::get, Router};
+/// use axum_debug::debug_path;
+///
+/// let app = Router::new().route(debug_path!("/users/{id}"), get(handler));
+/// ```
+///
+/// ```text
+/// error: `{id}` isn't a capture
+/// note: captures are written like `:id`
+///   --> main.rs:xx:39
+///    |
+/// xx | let app = Router::new().route(debug_path!("/users/{id}"), get(handler));
+///    |                                           ^^^^^^^^^^^^^
+/// ```
+///
+/// To also check that the `Path` extractor of a handler matches the captures, give the path to
+/// [`debug_handler`]:
+///
+/// ```rust,ignore
+/// #[debug_handler(path = "/users/:id")]
+/// async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+/// ```
+///
+/// ```text
+/// error: `Path` extracts 2 parameters, but `/users/:id` has 1 capture
+///   --> main.rs:xx:42
+///    |
+/// xx | async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+///    |                                      ^^^^^^^^^^^^^^^^
+/// ```
+///
+/// [`debug_handler`]: macro@debug_handler
+#[proc_macro]
+pub fn debug_path(input: TokenStream) -> TokenStream {
+    debug::apply_debug_path(input)
+}
+
+/// Checks that the handlers of a route extract the captures of its path.
+///
+/// Takes a path and a service like `Router::route` does, and evaluates to an
+/// `axum_debug::DebugRoute` that `RouterDebugExt::debug_route` adds to a router. The path is checked
+/// like [`debug_path`] does. The `Path` extractors of handlers must take as many values as there
+/// are captures, and structs using [`debug_path_params`] must have a field for each capture.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use axum::{extract::Path, handler::get, Router};
+/// use axum_debug::{debug_route, RouterDebugExt};
+///
+/// let app = Router::new().debug_route(debug_route!("/users/:id", get(handler)));
+///
+/// async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+/// ```
+///
+/// ```text
+/// error[E0080]: evaluation panicked: the number of captures in the route doesn't match the `Path` extractor
+///   --> axum-debug/src/path.rs
+///    |
+///    |     const OK: () = check_route::<T>(C::NAMES);
+///    |                    ^^^^^^^^^^^^^^^^^^^^^^^^^^ evaluation of `axum_debug::path::Checked::<(u32, u32), main::__AxumDebugCaptures>::OK` failed inside this call
+/// ```
+///
+/// The error is reported inside `axum_debug` and names the type of the `Path` extractor that
+/// doesn't match.
+///
+/// Handlers with a `Path` extractor of another type are checked if the type implements
+/// `axum_debug::PathParams`. Handlers are checked through their type, so any function or closure
+/// named in the route is, like `handlers::list`, `Controller::list` or a closure bound to a
+/// variable, whether it uses [`debug_handler`] or not. Closures written inline and handlers wrapped
+/// in layers are skipped, and so are the `debug_` wrappers of [`debug_handler`], which take the
+/// whole request.
+///
+/// The check runs when the route is compiled to code, so `cargo check` doesn't report it, but
+/// `cargo build` does.
+///
+/// [`debug_handler`]: macro@debug_handler
+/// [`debug_path`]: macro@debug_path
+/// [`debug_path_params`]: macro@debug_path_params
+#[proc_macro]
+pub fn debug_route(input: TokenStream) -> TokenStream {
+    debug::apply_debug_route(input)
+}
+
+/// Describes the path parameters a struct is deserialized from, for [`debug_route`] to check.
+///
+/// Field names are used as capture names, following `#[serde(rename = "..")]`. Only the number of
+/// fields is checked for tuple structs and structs using `#[serde(rename_all = "..")]`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use axum_debug::debug_path_params;
+/// use serde::Deserialize;
+///
+/// #[debug_path_params]
+/// #[derive(Deserialize)]
+/// struct Params {
+///     user: u32,
+///     #[serde(rename = "team")]
+///     team_id: u32,
+/// }
+/// ```
+///
+/// [`debug_route`]: macro@debug_route
+#[proc_macro_attribute]
+pub fn debug_path_params(attr: TokenStream, input: TokenStream) -> TokenStream {
+    debug::apply_debug_path_params(attr, input)
+}
+
+/// Records the handlers and extensions of a [`Router`] to be inspected in tests.
+///
+/// Takes any expression evaluating to a [`Router`] and evaluates to an `axum_debug::DebugRouter`
+/// holding it, along with the extensions and routes recorded while building it. Unlike
+/// [`debug_router`], the router isn't boxed and is recorded in release builds too.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use axum::{extract::Extension, handler::get, Router};
+/// use axum_debug::inspect_router;
+///
+/// #[test]
+/// fn extensions() {
+///     let router = inspect_router!(Router::new().route("/", get(handler)));
+///
+///     // Panics, because nothing adds `u32` to the handler.
+///     router.assert_extensions();
+/// }
+///
+/// async fn handler(Extension(count): Extension<u32>) {}
+/// ```
+///
+/// [`Router`]: axum::routing::Router
+/// [`debug_router`]: macro@debug_router
+#[proc_macro]
+pub fn inspect_router(input: TokenStream) -> TokenStream {
+    debug::apply_inspect_router(input)
+}
+
+/// Checks that the type it is applied to can be used as an extractor.
+///
+/// The type must implement `FromRequest` and [`Send`], and its rejection must implement
+/// `IntoResponse`. The item itself is left as it is. Like [`debug_handler`], checks are only done
+/// in debug builds unless `#[debug_extractor(always)]` or the `always` feature is used.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[debug_extractor]
+/// struct User {
+///     name: String,
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `User: FromRequest` is not satisfied
+///   --> main.rs:xx:8
+///    |
+/// xx | struct User {
+///    |        ^^^^ the trait `FromRequest` is not implemented for `User`
+/// ```
+///
+/// Generic types can't be checked this way, use `check_extractor` from `axum-debug` with concrete
+/// type parameters instead.
+///
+/// [`debug_handler`]: macro@debug_handler
+/// [`Send`]: Send
+#[proc_macro_attribute]
+pub fn debug_extractor(attr: TokenStream, input: TokenStream) -> TokenStream {
+    debug::apply_debug_extractor(attr, input)
+}
 
-    #[cfg(debug_assertions)]
-    return debug::apply_debug_router(_input);
+/// Checks that the type it is applied to can be returned from a handler.
+///
+/// The type must implement `IntoResponse` with a body producing `Bytes`. The item itself is left as
+/// it is. Like [`debug_handler`], checks are only done in debug builds unless
+/// `#[debug_response(always)]` or the `always` feature is used.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[debug_response]
+/// struct Page {
+///     html: String,
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `Page: IntoResponse` is not satisfied
+///   --> main.rs:xx:8
+///    |
+/// xx | struct Page {
+///    |        ^^^^ the trait `IntoResponse` is not implemented for `Page`
+/// ```
+///
+/// Generic types can't be checked this way, use `check_response` from `axum-debug` with concrete
+/// type parameters instead.
+///
+/// [`debug_handler`]: macro@debug_handler
+#[proc_macro_attribute]
+pub fn debug_response(attr: TokenStream, input: TokenStream) -> TokenStream {
+    debug::apply_debug_response(attr, input)
 }
 
-#[cfg(debug_assertions)]
 mod debug {
     use proc_macro::TokenStream;
-    use proc_macro2::Span;
-    use quote::{quote, quote_spanned};
-    use syn::{parse_macro_input, FnArg, Ident, ItemFn, ReturnType, Signature};
+    use proc_macro2::{Span, TokenTree};
+    use quote::{format_ident, quote, quote_spanned, ToTokens};
+    use syn::{
+        ext::IdentExt,
+        parenthesized,
+        parse::{Parse, ParseStream},
+        parse_macro_input, parse_quote, parse_quote_spanned,
+        punctuated::Punctuated,
+        visit::{self, Visit},
+        visit_mut::{self, VisitMut},
+        Attribute, Block, Data, DeriveInput, Expr, ExprAsync, ExprAwait, ExprCall, ExprClosure,
+        ExprMethodCall, ExprPath, Fields, FnArg, GenericArgument, GenericParam, Generics, Ident,
+        Item, ItemFn, Lit, LitStr, Meta, NestedMeta, Pat, PatType, PathArguments, ReturnType,
+        Signature, Stmt, Token, Type, TypeParamBound,
+    };
 
-    pub(crate) fn apply_debug_handler(input: TokenStream) -> TokenStream {
-        let function = parse_macro_input!(input as ItemFn);
+    /// Known extractors that consume the request body.
+    const BODY_EXTRACTORS: &[&str] = &[
+        "Json",
+        "Form",
+        "String",
+        "Bytes",
+        "BodyStream",
+        "Multipart",
+        "RawBody",
+        "Request",
+    ];
 
-        let vis = &function.vis;
-        let sig = &function.sig;
-        let ident = &sig.ident;
-        let span = ident.span();
-        let len = sig.inputs.len();
-        let generics = create_generics(len);
-        let params = sig.inputs.iter().map(|fn_arg| {
-            if let FnArg::Typed(pat_type) = fn_arg {
-                &pat_type.pat
-            } else {
-                panic!("not a handler function");
+    #[derive(Default)]
+    struct Args {
+        always: bool,
+        associated: bool,
+        rejections: bool,
+        trace: bool,
+        path: Option<LitStr>,
+        body: Vec<Ident>,
+        result: Vec<Ident>,
+    }
+
+    impl Parse for Args {
+        fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+            let mut args = Args::default();
+
+            while !input.is_empty() {
+                let ident: Ident = input.parse()?;
+
+                if ident == "always" {
+                    args.always = true;
+                } else if ident == "associated" {
+                    args.associated = true;
+                } else if ident == "rejections" {
+                    args.rejections = true;
+                } else if ident == "trace" {
+                    args.trace = true;
+                } else if ident == "path" {
+                    input.parse::<Token![=]>()?;
+                    args.path = Some(input.parse()?);
+                } else if ident == "body" {
+                    let content;
+                    parenthesized!(content in input);
+                    args.body
+                        .extend(Punctuated::<Ident, Token![,]>::parse_terminated(&content)?);
+                } else if ident == "result" {
+                    let content;
+                    parenthesized!(content in input);
+                    args.result
+                        .extend(Punctuated::<Ident, Token![,]>::parse_terminated(&content)?);
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unknown argument, expected `always`, `associated`, `rejections`, `trace`, `path = \"..\"`, `body(..)` or `result(..)`",
+                    ));
+                }
+
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
             }
-        });
-        let block = &function.block;
 
-        if let Err(error) = async_check(&sig) {
-            return error;
+            Ok(args)
         }
+    }
 
-        if let Err(error) = param_limit_check(&sig) {
-            return error;
-        }
+    /// Arguments of `debug_extractor` and `debug_response`.
+    #[derive(Default)]
+    struct TypeArgs {
+        always: bool,
+    }
 
-        let check_trait = check_trait_code(&sig, &generics);
-        let check_return = check_return_code(&sig, &generics);
-        let check_params = check_params_code(&sig, &generics);
+    impl Parse for TypeArgs {
+        fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+            let mut args = TypeArgs::default();
 
-        let expanded = quote_spanned! {span=>
-            #vis #sig {
-                #check_trait
-                #check_return
-                #(#check_params)*
+            while !input.is_empty() {
+                let ident: Ident = input.parse()?;
 
-                #sig #block
+                if ident == "always" {
+                    args.always = true;
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unknown argument, expected `always`",
+                    ));
+                }
 
-                #ident(#(#params),*).await
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
             }
-        };
 
-        expanded.into()
+            Ok(args)
+        }
     }
 
-    pub(crate) fn apply_debug_router(input: TokenStream) -> TokenStream {
-        let ident = parse_macro_input!(input as Ident);
+    /// Attribute limiting generated code to debug builds of the crate using the macros, unless
+    /// checks are always enabled.
+    fn debug_cfg(always: bool) -> proc_macro2::TokenStream {
+        if always || cfg!(feature = "always") {
+            quote!()
+        } else {
+            quote!(#[cfg(debug_assertions)])
+        }
+    }
 
-        let expanded = quote! {
-            let #ident = axum::Router::boxed(#ident);
-        };
+    /// The `cfg` attributes of an item, to put on the items generated next to it. Other attributes
+    /// are dropped from `cfg_attr`, leaving `#[cfg_attr(predicate, cfg(..))]`.
+    fn item_cfgs(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+        let mut cfgs = proc_macro2::TokenStream::new();
 
-        expanded.into()
-    }
+        for attr in attrs {
+            if attr.path.is_ident("cfg") {
+                attr.to_tokens(&mut cfgs);
+            } else if attr.path.is_ident("cfg_attr") {
+                let group = match attr.tokens.clone().into_iter().next() {
+                    Some(TokenTree::Group(group)) => group,
+                    _ => continue,
+                };
 
-    fn create_generics(len: usize) -> Vec<Ident> {
-        let mut vec = Vec::new();
-        for i in 1..=len {
-            vec.push(Ident::new(&format!("T{}", i), Span::call_site()));
+                let mut parts = split_commas(group.stream()).into_iter();
+                let predicate = match parts.next() {
+                    Some(predicate) => predicate,
+                    None => continue,
+                };
+                let nested: Vec<_> = parts
+                    .filter(|part| {
+                        matches!(part.clone().into_iter().next(), Some(TokenTree::Ident(ident)) if ident == "cfg")
+                    })
+                    .collect();
+
+                if !nested.is_empty() {
+                    cfgs.extend(quote!(#[cfg_attr(#predicate, #(#nested),*)]));
+                }
+            }
         }
-        vec
+
+        cfgs
     }
 
-    fn async_check(sig: &Signature) -> Result<(), TokenStream> {
-        if sig.asyncness.is_none() {
-            let error = syn::Error::new_spanned(sig.fn_token, "handlers must be async functions")
-                .to_compile_error()
-                .into();
+    /// Splits `tokens` at commas that aren't nested in a group.
+    fn split_commas(tokens: proc_macro2::TokenStream) -> Vec<proc_macro2::TokenStream> {
+        let mut parts = vec![proc_macro2::TokenStream::new()];
 
-            return Err(error);
+        for token in tokens {
+            match &token {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                    parts.push(proc_macro2::TokenStream::new())
+                }
+                _ => parts.last_mut().unwrap().extend(Some(token)),
+            }
         }
 
-        Ok(())
+        parts.retain(|part| !part.is_empty());
+        parts
     }
 
-    fn param_limit_check(sig: &Signature) -> Result<(), TokenStream> {
-        if sig.inputs.len() > 16 {
-            let msg = "too many extractors. 16 extractors are allowed\n\
-                       note: you can nest extractors like \"a: (Extractor, Extractor), b: (Extractor, Extractor)\"";
+    pub(crate) fn apply_debug_handler(attr: TokenStream, input: TokenStream) -> TokenStream {
+        let args = parse_macro_input!(attr as Args);
+        let function = parse_macro_input!(input as ItemFn);
+        let cfg = debug_cfg(args.always);
 
-            let error = syn::Error::new_spanned(&sig.inputs, msg)
-                .to_compile_error()
-                .into();
+        let expanded = match expand_debug_handler(&args, &function, &cfg) {
+            Ok(expanded) => expanded,
+            Err(error) => {
+                let errors = error.into_iter().map(|error| {
+                    let error = error.to_compile_error();
+                    quote!(#cfg #error)
+                });
 
-            return Err(error);
-        }
+                // The function is kept so code using it doesn't report more errors.
+                quote! {
+                    #(#errors)*
+                    #function
+                }
+            }
+        };
 
-        Ok(())
+        expanded.into()
     }
 
-    fn check_trait_code(sig: &Signature, generics: &Vec<Ident>) -> proc_macro2::TokenStream {
+    /// Emits the handler next to an item holding the checks. Only the `Send` assertions of
+    /// handlers checked through `Self` have to be inside the handler's body.
+    fn expand_debug_handler(
+        args: &Args,
+        function: &ItemFn,
+        cfg: &proc_macro2::TokenStream,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let sig = &function.sig;
         let ident = &sig.ident;
         let span = ident.span();
+        let len = sig.inputs.len();
+        let generics = create_generics(len);
+        let turbofish = turbofish(&sig.generics);
+        let (impl_generics, _, where_clause) = sig.generics.split_for_impl();
 
-        quote_spanned! {span=>
-            {
-                debug_handler(#ident);
+        async_check(sig)?;
+        receiver_check(sig)?;
+        param_limit_check(sig)?;
+        body_extractor_check(sig, args)?;
 
-                fn debug_handler<F, Fut, #(#generics),*>(_f: F)
+        if let Some(path) = &args.path {
+            path_extractor_check(sig, path)?;
+        }
+
+        let mut function = function.clone();
+        let checked_block = check_send_code(&function.block, cfg);
+
+        let check_ident = format_ident!("__axum_debug_check_{}", ident);
+
+        // The checks are in a hidden function, which is valid in modules and `impl` blocks alike.
+        // Functions mentioning `Self` are in an `impl` block and are called through `Self`. Others
+        // may be in either, so the checks call a copy of the function nested in them instead.
+        let associated = args.associated || uses_self(&function);
+        // The `Send` assertions only go in the function called by the checks, so they are reported
+        // once.
+        let (handler, copy) = if associated {
+            *function.block = checked_block;
+
+            (quote!(Self::#ident #turbofish), quote!())
+        } else {
+            let copy_sig = &function.sig;
+
+            (quote!(#ident #turbofish), quote!(#copy_sig #checked_block))
+        };
+
+        let check_trait = check_trait_code(sig, &handler, &generics);
+        let check_return = check_return_code(sig, &handler, &generics, args);
+        let check_params = check_params_code(sig, &handler, &generics);
+
+        let check = quote_spanned! {span=>
+            #[allow(warnings)]
+            fn #check_ident #impl_generics() #where_clause {
+                #copy
+
+                #check_trait
+                #(#check_return)*
+                #(#check_params)*
+            }
+        };
+
+        let wrapper = if args.rejections || args.trace {
+            wrapper_code(&function, args, &handler)?
+        } else {
+            quote!()
+        };
+
+        let cfgs = item_cfgs(&function.attrs);
+        let expanded = quote! {
+            #function
+
+            #cfg
+            #cfgs
+            #[doc(hidden)]
+            #check
+
+            #wrapper
+        };
+
+        Ok(expanded)
+    }
+
+    /// Emits `debug_{handler}`, a handler wrapping the handler for `rejections` and `trace`. The
+    /// handler itself is left as written.
+    fn wrapper_code(
+        function: &ItemFn,
+        args: &Args,
+        handler: &proc_macro2::TokenStream,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let sig = &function.sig;
+        let ident = &sig.ident;
+        let mode = if args.trace { "trace" } else { "rejections" };
+
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                format!("`{}` can't be used with generic handlers", mode),
+            ));
+        }
+
+        let wrapper = format_ident!("debug_{}", ident.unraw(), span = ident.span());
+        let deprecated = function
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("deprecated"));
+        let doc = format!(
+            "[`{}`] wrapped by `#[debug_handler({})]`, to be routed instead of it.",
+            ident.unraw(),
+            if args.trace && args.rejections {
+                "trace, rejections"
+            } else {
+                mode
+            }
+        );
+        let vis = &function.vis;
+
+        let (signature, body) = wrapper_body_code(sig, handler, &wrapper, args);
+
+        let cfgs = item_cfgs(&function.attrs);
+
+        let expanded = quote! {
+            #cfgs
+            #[doc = #doc]
+            #(#deprecated)*
+            #vis #signature {
+                #body
+            }
+        };
+
+        Ok(expanded)
+    }
+
+    /// Wrapper taking the whole request and running the extractors itself, one at a time, so it
+    /// can stop at the first rejection like axum does. With `trace`, each extractor runs in its
+    /// own span, followed by the handler in a `body` span, all inside a span named after the
+    /// handler.
+    fn wrapper_body_code(
+        sig: &Signature,
+        handler: &proc_macro2::TokenStream,
+        wrapper: &Ident,
+        args: &Args,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let ident = &sig.ident;
+        let req = Ident::new("req", Span::mixed_site());
+        let parts = Ident::new("parts", Span::mixed_site());
+        let span = Ident::new("span", Span::mixed_site());
+        let future = Ident::new("future", Span::mixed_site());
+        let handler_fn = Ident::new("handler", Span::mixed_site());
+        let body = Ident::new("body", Span::mixed_site());
+        let values: Vec<Ident> = (0..sig.inputs.len())
+            .map(|i| format_ident!("arg{}", i, span = Span::mixed_site()))
+            .collect();
+        let types: Vec<&Type> = typed_inputs(sig).map(|input| &*input.ty).collect();
+        let positions: Vec<usize> = (1..=values.len()).collect();
+
+        // The body is kept to find the field `Json` and `Form` extractors failed on.
+        let buffered = args.rejections && types.iter().any(|ty| deserializes_body(ty));
+        let body_arg = if buffered {
+            quote!(std::option::Option::Some(&#body))
+        } else {
+            quote!(std::option::Option::None)
+        };
+        let buffer = if buffered {
+            quote! {
+                let (#req, #body) = match axum_debug::__private::buffer_body(#req).await {
+                    std::result::Result::Ok(buffered) => buffered,
+                    std::result::Result::Err(response) => return response,
+                };
+            }
+        } else {
+            quote!()
+        };
+
+        let extract: Vec<proc_macro2::TokenStream> = if args.trace {
+            typed_inputs(sig)
+                .zip(&positions)
+                .map(|(input, position)| {
+                    let ty = &input.ty;
+                    let name = format!(
+                        "{}: {}",
+                        pretty_tokens(&input.pat),
+                        pretty_tokens(&input.ty)
+                    );
+                    let name = LitStr::new(&name, Span::call_site());
+
+                    quote! {
+                        axum_debug::__private::trace_extractor::<#ty>(
+                            &mut #parts,
+                            axum_debug::__private::tracing::info_span!(
+                                #name,
+                                argument = #position,
+                                extractor = axum_debug::__private::tracing::field::Empty,
+                                outcome = axum_debug::__private::tracing::field::Empty,
+                                rejection = axum_debug::__private::tracing::field::Empty,
+                                elapsed = axum_debug::__private::tracing::field::Empty,
+                            ),
+                        )
+                    }
+                })
+                .collect()
+        } else {
+            types
+                .iter()
+                .map(|ty| {
+                    quote! {
+                        <#ty as axum::extract::FromRequest<axum::body::Body>>::from_request(
+                            &mut #parts,
+                        )
+                    }
+                })
+                .collect()
+        };
+
+        let reject: Vec<proc_macro2::TokenStream> = types
+            .iter()
+            .zip(&positions)
+            .map(|(ty, position)| {
+                if args.rejections {
+                    quote! {
+                        axum_debug::__private::explain_rejection::<#ty, _>(
+                            std::concat!(std::module_path!(), "::", std::stringify!(#ident)),
+                            #position,
+                            rejection,
+                            {
+                                #[allow(unused_imports)]
+                                use axum_debug::__private::{FieldPath as _, NoFieldPath as _};
+                                (&axum_debug::__private::rejected::<#ty>())
+                                    .field(&#parts, #body_arg)
+                            },
+                        )
+                    }
+                } else {
+                    quote! {
+                        axum::response::IntoResponse::into_response(rejection)
+                            .map(axum::body::box_body)
+                    }
+                }
+            })
+            .collect();
+
+        let call = if args.trace {
+            quote! {
+                axum_debug::__private::trace_handler(
+                    #handler_fn(#(#values),*),
+                    axum_debug::__private::tracing::info_span!(
+                        "body",
+                        status = axum_debug::__private::tracing::field::Empty,
+                        elapsed = axum_debug::__private::tracing::field::Empty,
+                    ),
+                )
+                .await
+            }
+        } else {
+            quote! {
+                axum::response::IntoResponse::into_response(#handler_fn(#(#values),*).await)
+                    .map(axum::body::box_body)
+            }
+        };
+
+        let signature = quote! {
+            async fn #wrapper(
+                #req: axum::http::Request<axum::body::Body>,
+            ) -> axum::http::Response<axum::body::BoxBody>
+        };
+
+        // The wrapper carries the handler's `deprecated` attribute for its callers.
+        let extract_and_call = quote! {
+            #[allow(deprecated)]
+            let #handler_fn = #handler;
+            #buffer
+            let mut #parts = axum::extract::RequestParts::new(#req);
+
+            #(
+                let #values = match #extract.await {
+                    std::result::Result::Ok(value) => value,
+                    std::result::Result::Err(rejection) => return #reject,
+                };
+            )*
+
+            #call
+        };
+
+        let body = if args.trace {
+            let handler_name = LitStr::new(&ident.unraw().to_string(), ident.span());
+
+            quote! {
+                let #span = axum_debug::__private::tracing::info_span!(#handler_name);
+
+                let #future = async move {
+                    #extract_and_call
+                };
+
+                axum_debug::__private::tracing::Instrument::instrument(#future, #span).await
+            }
+        } else {
+            extract_and_call
+        };
+
+        (signature, body)
+    }
+
+    pub(crate) fn apply_debug_router(input: TokenStream) -> TokenStream {
+        let router = parse_macro_input!(input as Expr);
+        let cfg = debug_cfg(false);
+        let ident = Ident::new("router", Span::mixed_site());
+        let info = Ident::new("info", Span::mixed_site());
+        let recorded = record_router(&router, &info);
+
+        let release = if cfg.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                #[cfg(not(debug_assertions))]
+                let #ident = axum::Router::boxed(#router);
+            }
+        };
+
+        let expanded = quote! {
+            {
+                #cfg
+                let #ident = {
+                    let #info = axum_debug::__private::RouterInfo::default();
+                    let #ident = axum::Router::boxed(#recorded);
+                    #info.report();
+                    #ident
+                };
+                #release
+                #ident
+            }
+        };
+
+        expanded.into()
+    }
+
+    pub(crate) fn apply_debug_path(input: TokenStream) -> TokenStream {
+        let path = parse_macro_input!(input as LitStr);
+        let cfg = debug_cfg(false);
+
+        let expanded = match parse_route_path(&path.value()) {
+            Ok(_) => quote!(#path),
+            Err(msg) => {
+                let error = syn::Error::new_spanned(&path, msg).to_compile_error();
+
+                quote! {
+                    {
+                        #cfg
+                        #error
+                        #path
+                    }
+                }
+            }
+        };
+
+        expanded.into()
+    }
+
+    /// Hidden constant whose type names the type the handler's `Path` extractor takes, or `()` if it
+    /// doesn't have one, for `debug_route!` to check against routes. A constant rather than a type
+    /// alias, since it is valid in `impl` blocks too.
+    struct RouteInput {
+        path: LitStr,
+        service: Expr,
+    }
+
+    impl Parse for RouteInput {
+        fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+            let path = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let service = input.parse()?;
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+
+            Ok(RouteInput { path, service })
+        }
+    }
+
+    pub(crate) fn apply_debug_route(input: TokenStream) -> TokenStream {
+        let RouteInput { path, service } = parse_macro_input!(input as RouteInput);
+        let cfg = debug_cfg(false);
+
+        let checks = match parse_route_path(&path.value()) {
+            Ok(captures) => {
+                let names = captures.iter().map(|capture| &*capture.name);
+                let captures = Ident::new("__AxumDebugCaptures", Span::mixed_site());
+                let args = Ident::new("args", Span::mixed_site());
+                let mut handlers = RouteHandlers(Vec::new());
+                handlers.visit_expr(&service);
+
+                // Handlers take at most 16 arguments, each of which could be a `Path` extractor.
+                let positions: Vec<proc_macro2::TokenStream> = (0..16)
+                    .map(|position| {
+                        let rest = (0..position).map(|_| quote!(.rest()));
+                        quote!(#args #(#rest)* .arg())
+                    })
+                    .collect();
+
+                let checks = handlers.0.into_iter().map(|handler| {
+                    let span = syn::Error::new_spanned(handler, "").span();
+
+                    quote_spanned! {span=>
+                        let #args = (&axum_debug::__private::probe(&#handler)).args();
+                        #(
+                            axum_debug::__private::check_arg::<#captures, _>(
+                                (&#positions).path(),
+                            );
+                        )*
+                    }
+                });
+
+                quote! {
+                    #cfg
+                    let () = {
+                        #[allow(unused_imports)]
+                        use axum_debug::__private::{
+                            OtherArg as _, PathArg as _, ProbeHandler as _, ProbeService as _,
+                        };
+
+                        struct #captures;
+
+                        impl axum_debug::__private::Captures for #captures {
+                            const NAMES: &'static [&'static str] = &[#(#names),*];
+                        }
+
+                        #(#checks)*
+                    };
+                }
+            }
+            Err(msg) => syn::Error::new_spanned(&path, msg).to_compile_error(),
+        };
+
+        let expanded = quote! {
+            {
+                #checks
+
+                axum_debug::__private::route(#path, #service)
+            }
+        };
+
+        expanded.into()
+    }
+
+    /// Handler functions in a route's service, like `handler` in `get(handler)`.
+    struct RouteHandlers<'a>(Vec<&'a ExprPath>);
+
+    impl<'a> RouteHandlers<'a> {
+        fn push_handler(&mut self, method: &Ident, args: &'a Punctuated<Expr, Token![,]>) {
+            if !HANDLER_METHODS.iter().any(|name| method == name) {
+                return;
+            }
+
+            // Handlers are named to be checked before the route is built. Other expressions, like
+            // closures, could move values or have side effects if written twice.
+            if let Some(Expr::Path(handler)) = args.iter().last() {
+                self.0.push(handler);
+            }
+        }
+    }
+
+    impl<'a> Visit<'a> for RouteHandlers<'a> {
+        fn visit_expr_call(&mut self, call: &'a ExprCall) {
+            visit::visit_expr_call(self, call);
+
+            match call_segments(call) {
+                Some(segments) if !is_service(&segments) => {
+                    if let Some(method) = segments.last() {
+                        self.push_handler(method, &call.args);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        fn visit_expr_method_call(&mut self, call: &'a ExprMethodCall) {
+            visit::visit_expr_method_call(self, call);
+
+            // Handlers for more methods are chained on the result of a function like `get`.
+            if let Some(segments) = root_call(&call.receiver).and_then(call_segments) {
+                match segments.last() {
+                    Some(root)
+                        if HANDLER_METHODS.iter().any(|name| root == name)
+                            && !is_service(&segments) =>
+                    {
+                        self.push_handler(&call.method, &call.args);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn visit_expr_async(&mut self, _: &'a ExprAsync) {}
+
+        fn visit_expr_closure(&mut self, _: &'a ExprClosure) {}
+
+        fn visit_item(&mut self, _: &'a Item) {}
+    }
+
+    pub(crate) fn apply_debug_path_params(attr: TokenStream, input: TokenStream) -> TokenStream {
+        let args = parse_macro_input!(attr as TypeArgs);
+        let item = parse_macro_input!(input as DeriveInput);
+        let cfg = debug_cfg(args.always);
+
+        let params = match path_params_code(&item) {
+            Ok(params) => params,
+            Err(error) => error.to_compile_error(),
+        };
+
+        let cfgs = item_cfgs(&item.attrs);
+        let expanded = quote! {
+            #item
+
+            #cfg
+            #cfgs
+            #params
+        };
+
+        expanded.into()
+    }
+
+    fn path_params_code(item: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+        let fields = match &item.data {
+            Data::Struct(data) => &data.fields,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &item.ident,
+                    "`debug_path_params` can only be used on structs",
+                ))
+            }
+        };
+
+        let count = fields.len();
+        let names = match fields {
+            // Renaming every field isn't followed, so names aren't checked then.
+            Fields::Named(fields) if serde_value(&item.attrs, "rename_all")?.is_none() => {
+                let names = fields
+                    .named
+                    .iter()
+                    .map(|field| match serde_value(&field.attrs, "rename")? {
+                        Some(name) => Ok(name),
+                        None => Ok(field.ident.as_ref().unwrap().unraw().to_string()),
+                    })
+                    .collect::<syn::Result<Vec<String>>>()?;
+
+                quote!(Some(&[#(#names),*]))
+            }
+            _ => quote!(None),
+        };
+
+        let ident = &item.ident;
+        let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+        Ok(quote! {
+            impl #impl_generics axum_debug::PathParams for #ident #ty_generics #where_clause {
+                const COUNT: Option<usize> = Some(#count);
+                const NAMES: Option<&'static [&'static str]> = #names;
+            }
+        })
+    }
+
+    /// Value of `#[serde(name = "value")]` in `attrs`.
+    fn serde_value(attrs: &[Attribute], name: &str) -> syn::Result<Option<String>> {
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                _ => continue,
+            };
+
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+                    if name_value.path.is_ident(name) {
+                        if let Lit::Str(value) = name_value.lit {
+                            return Ok(Some(value.value()));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn apply_inspect_router(input: TokenStream) -> TokenStream {
+        let router = parse_macro_input!(input as Expr);
+        let info = Ident::new("info", Span::mixed_site());
+        let recorded = record_router(&router, &info);
+
+        let expanded = quote! {
+            {
+                let #info = axum_debug::__private::RouterInfo::default();
+                axum_debug::__private::inspect(#recorded, #info)
+            }
+        };
+
+        expanded.into()
+    }
+
+    /// Methods and functions taking handlers in axum.
+    const HANDLER_METHODS: &[&str] = &[
+        "any", "delete", "get", "head", "on", "options", "patch", "post", "put", "trace",
+    ];
+
+    /// Copies the router expression with the routes, handlers and extensions in it recorded into
+    /// `info`.
+    fn record_router(router: &Expr, info: &Ident) -> Expr {
+        let mut router = router.clone();
+        RecordRouter(info).visit_expr_mut(&mut router);
+        router
+    }
+
+    struct RecordRouter<'a>(&'a Ident);
+
+    impl RecordRouter<'_> {
+        fn record(&self, function: &str, arg: &mut Expr, extra: proc_macro2::TokenStream) {
+            let info = self.0;
+            let function = Ident::new(function, Span::call_site());
+            let span = syn::Error::new_spanned(&*arg, "").span();
+
+            *arg = parse_quote_spanned! {span=>
+                axum_debug::__private::#function(&#info, #extra #arg)
+            };
+        }
+
+        fn record_handlers(
+            &self,
+            method: &Ident,
+            args: &mut Punctuated<Expr, Token![,]>,
+            service: bool,
+        ) {
+            if !HANDLER_METHODS.iter().any(|name| method == name) {
+                return;
+            }
+
+            let name = if method == "on" {
+                match args.first() {
+                    Some(filter) => method_filter(filter),
+                    None => return,
+                }
+            } else {
+                method.to_string().to_uppercase()
+            };
+
+            // `on` takes a method filter before the handler.
+            let handler = match args.iter_mut().last() {
+                Some(handler) => handler,
+                None => return,
+            };
+
+            if service {
+                self.record("record_service", handler, quote!(#name,));
+            } else {
+                self.record_handler(handler, &name);
+            }
+        }
+
+        /// Records the extractors of functions and closures. Anything else, like a handler wrapped
+        /// in layers or a service passed to `get` from `axum::service`, is recorded as a service.
+        fn record_handler(&self, handler: &mut Expr, method: &str) {
+            let info = self.0;
+            let span = syn::Error::new_spanned(&*handler, "").span();
+
+            *handler = parse_quote_spanned! {span=>
+                {
+                    #[allow(unused_imports)]
+                    use axum_debug::__private::{RecordHandler as _, RecordService as _};
+
+                    (&axum_debug::__private::record_handler(&#info, #method, #handler)).record()
+                }
+            };
+        }
+    }
+
+    impl VisitMut for RecordRouter<'_> {
+        fn visit_expr_call_mut(&mut self, call: &mut ExprCall) {
+            visit_mut::visit_expr_call_mut(self, call);
+
+            let segments = match call_segments(call) {
+                Some(segments) => segments,
+                None => return,
+            };
+
+            match segments.as_slice() {
+                [.., layer, new] if layer == "AddExtensionLayer" && new == "new" => {
+                    if let Some(value) = call.args.first_mut() {
+                        self.record("record_extension", value, quote!());
+                    }
+                }
+                [.., method] => {
+                    let service = is_service(&segments);
+                    self.record_handlers(method, &mut call.args, service);
+                }
+                _ => {}
+            }
+        }
+
+        fn visit_expr_method_call_mut(&mut self, call: &mut ExprMethodCall) {
+            visit_mut::visit_expr_method_call_mut(self, call);
+
+            let method = &call.method;
+            let args = &mut call.args;
+
+            if method == "route" && args.len() == 2 {
+                self.record("record_route", &mut args[0], quote!());
+            } else if method == "nest" && args.len() == 2 {
+                // Arguments are evaluated in order, so everything recorded in between is nested.
+                self.record("record_nest", &mut args[0], quote!());
+                self.record("record_nest_end", &mut args[1], quote!());
+            } else if method == "or" && args.len() == 1 {
+                // Marks where both routers start, since the routes of the first are tried first.
+                let info = self.0;
+                let receiver = &call.receiver;
+                let other = &args[0];
+
+                *call.receiver = parse_quote! {
+                    {
+                        axum_debug::__private::record_or(&#info);
+                        #receiver
+                    }
+                };
+                args[0] = parse_quote! {
+                    axum_debug::__private::record_or_end(&#info, {
+                        axum_debug::__private::record_or_else(&#info);
+                        #other
+                    })
+                };
+            } else if let Some(segments) = root_call(&call.receiver).and_then(call_segments) {
+                // Handlers for more methods are chained on the result of a function like `get`.
+                match segments.last() {
+                    Some(root) if HANDLER_METHODS.iter().any(|name| root == name) => {
+                        let service = is_service(&segments);
+                        self.record_handlers(&call.method, &mut call.args, service);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Code inside these doesn't run while building the router.
+        fn visit_expr_async_mut(&mut self, _: &mut ExprAsync) {}
+
+        fn visit_expr_closure_mut(&mut self, _: &mut ExprClosure) {}
+
+        fn visit_item_mut(&mut self, _: &mut Item) {}
+    }
+
+    /// Path segments of the function being called.
+    fn call_segments(call: &ExprCall) -> Option<Vec<Ident>> {
+        match &*call.func {
+            Expr::Path(func) => Some(
+                func.path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.clone())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Function call a chain of method calls starts with.
+    fn root_call(mut expr: &Expr) -> Option<&ExprCall> {
+        loop {
+            match expr {
+                Expr::MethodCall(call) => expr = &call.receiver,
+                Expr::Call(call) => return Some(call),
+                _ => return None,
+            }
+        }
+    }
+
+    /// Functions taking services instead of handlers live in `service` modules.
+    fn is_service(segments: &[Ident]) -> bool {
+        segments.iter().any(|segment| segment == "service")
+    }
+
+    /// Methods in a method filter like `MethodFilter::GET | MethodFilter::POST`, joined with `|`.
+    fn method_filter(filter: &Expr) -> String {
+        fn methods(tokens: proc_macro2::TokenStream, vec: &mut Vec<String>) {
+            for tt in tokens {
+                match tt {
+                    TokenTree::Ident(ident) => {
+                        let ident = ident.to_string();
+
+                        if ident.chars().all(|c| c.is_ascii_uppercase()) {
+                            vec.push(ident);
+                        }
+                    }
+                    TokenTree::Group(group) => methods(group.stream(), vec),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut vec = Vec::new();
+        methods(filter.to_token_stream(), &mut vec);
+
+        if vec.is_empty() {
+            filter.to_token_stream().to_string()
+        } else {
+            vec.join("|")
+        }
+    }
+
+    pub(crate) fn apply_debug_extractor(attr: TokenStream, input: TokenStream) -> TokenStream {
+        let args = parse_macro_input!(attr as TypeArgs);
+        let item = parse_macro_input!(input as DeriveInput);
+        let ident = &item.ident;
+
+        let check = quote_spanned! {ident.span()=>
+            debug_extractor::<#ident>();
+
+            fn debug_extractor<T>()
+            where
+                T: axum::extract::FromRequest + Send,
+                T::Rejection: axum::response::IntoResponse,
+            {}
+        };
+
+        expand_debug_type("debug_extractor", &args, &item, check).into()
+    }
+
+    pub(crate) fn apply_debug_response(attr: TokenStream, input: TokenStream) -> TokenStream {
+        let args = parse_macro_input!(attr as TypeArgs);
+        let item = parse_macro_input!(input as DeriveInput);
+        let ident = &item.ident;
+
+        let check = quote_spanned! {ident.span()=>
+            debug_response::<#ident>();
+
+            fn debug_response<T>()
+            where
+                T: axum::response::IntoResponse,
+                T::Body: axum::body::HttpBody<Data = axum::body::Bytes> + Send + Sync + 'static,
+                <T::Body as axum::body::HttpBody>::Error:
+                    Into<Box<dyn std::error::Error + Send + Sync>>,
+            {}
+        };
+
+        expand_debug_type("debug_response", &args, &item, check).into()
+    }
+
+    /// Emits the type as written, next to an item running `check` on it.
+    fn expand_debug_type(
+        name: &str,
+        args: &TypeArgs,
+        item: &DeriveInput,
+        check: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let cfg = debug_cfg(args.always);
+
+        if !item.generics.params.is_empty() {
+            let msg = format!(
+                "`{}` can't check generic types\n\
+                 note: use `axum_debug::check_{}` with concrete type parameters instead",
+                name,
+                name.trim_start_matches("debug_")
+            );
+            let error = syn::Error::new_spanned(&item.generics, msg).to_compile_error();
+
+            return quote! {
+                #cfg
+                #error
+
+                #item
+            };
+        }
+
+        let cfgs = item_cfgs(&item.attrs);
+
+        quote! {
+            #item
+
+            #cfg
+            #cfgs
+            const _: () = {
+                #[allow(warnings)]
+                fn check() {
+                    #check
+                }
+            };
+        }
+    }
+
+    fn create_generics(len: usize) -> Vec<Ident> {
+        let mut vec = Vec::new();
+        for i in 1..=len {
+            vec.push(Ident::new(&format!("T{}", i), Span::call_site()));
+        }
+        vec
+    }
+
+    fn async_check(sig: &Signature) -> syn::Result<()> {
+        if sig.asyncness.is_none() {
+            let error = syn::Error::new_spanned(sig.fn_token, "handlers must be async functions");
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    fn receiver_check(sig: &Signature) -> syn::Result<()> {
+        if let Some(FnArg::Receiver(receiver)) = sig.inputs.first() {
+            let msg = "handlers cannot take `self`\n\
+                       note: use an associated function without a receiver instead";
+
+            let error = syn::Error::new_spanned(receiver, msg);
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    fn param_limit_check(sig: &Signature) -> syn::Result<()> {
+        if sig.inputs.len() > 16 {
+            let msg = "too many extractors. 16 extractors are allowed\n\
+                       note: you can nest extractors like \"a: (Extractor, Extractor), b: (Extractor, Extractor)\"";
+
+            let error = syn::Error::new_spanned(&sig.inputs, msg);
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    fn body_extractor_check(sig: &Signature, args: &Args) -> syn::Result<()> {
+        let inputs: Vec<&PatType> = typed_inputs(sig).collect();
+        let body: Vec<(usize, &PatType, &Ident)> = inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pat_type)| {
+                body_extractor(&pat_type.ty, args).map(|ident| (i, *pat_type, ident))
+            })
+            .collect();
+
+        let mut error: Option<syn::Error> = None;
+        let mut push = |new: syn::Error| match &mut error {
+            Some(error) => error.combine(new),
+            None => error = Some(new),
+        };
+
+        match body.as_slice() {
+            [] => {}
+            [(i, pat_type, ident)] => {
+                if *i != inputs.len() - 1 {
+                    let msg = format!(
+                        "`{}` consumes the request body and must be the last argument",
+                        ident
+                    );
+
+                    push(syn::Error::new_spanned(&pat_type.ty, msg));
+                }
+            }
+            [(_, _, first), rest @ ..] => {
+                for (_, pat_type, ident) in rest {
+                    let msg = format!(
+                        "`{}` consumes the request body, but `{}` already does\n\
+                         note: only one extractor can consume the request body",
+                        ident, first
+                    );
+
+                    push(syn::Error::new_spanned(&pat_type.ty, msg));
+                }
+            }
+        }
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Name of the body extractor `ty` is or contains, looking through `Option`, `Result` and
+    /// tuples of extractors.
+    fn body_extractor<'a>(ty: &'a Type, args: &'a Args) -> Option<&'a Ident> {
+        match ty {
+            Type::Path(type_path) => {
+                let segment = type_path.path.segments.last()?;
+                let ident = &segment.ident;
+
+                if ident == "Option" || ident == "Result" {
+                    match &segment.arguments {
+                        PathArguments::AngleBracketed(arguments) => {
+                            match arguments.args.first()? {
+                                GenericArgument::Type(ty) => body_extractor(ty, args),
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                } else if BODY_EXTRACTORS.iter().any(|name| ident == name)
+                    || args.body.contains(ident)
+                {
+                    Some(ident)
+                } else {
+                    None
+                }
+            }
+            Type::Paren(type_paren) => body_extractor(&type_paren.elem, args),
+            Type::Group(type_group) => body_extractor(&type_group.elem, args),
+            Type::Tuple(type_tuple) => type_tuple
+                .elems
+                .iter()
+                .find_map(|ty| body_extractor(ty, args)),
+            _ => None,
+        }
+    }
+
+    /// Types that are extracted from a single path capture.
+    const PATH_SCALARS: &[&str] = &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+        "u32", "u64", "u128", "usize", "String", "Uuid",
+    ];
+
+    /// Capture in a route path, like `:id` or `*rest`.
+    struct Capture {
+        name: String,
+    }
+
+    /// Validates a route path the way axum's router reads it and returns its captures.
+    fn parse_route_path(path: &str) -> Result<Vec<Capture>, String> {
+        if !path.starts_with('/') {
+            return Err(format!("`{}` must start with `/`", path));
+        }
+
+        let segments: Vec<&str> = path[1..].split('/').collect();
+        let mut captures: Vec<Capture> = Vec::new();
+
+        for (i, segment) in segments.iter().enumerate() {
+            let last = i == segments.len() - 1;
+
+            if segment.is_empty() {
+                // A trailing slash is fine.
+                if last {
+                    continue;
+                }
+
+                return Err(format!("`{}` has an empty segment", path));
+            }
+
+            if segment.contains('{') || segment.contains('}') {
+                let name = segment.trim_matches(|c| c == '{' || c == '}');
+
+                return Err(format!(
+                    "`{}` isn't a capture\nnote: captures are written like `:{}`",
+                    segment, name
+                ));
+            }
+
+            let (name, wildcard) = match segment.as_bytes()[0] {
+                b':' => (&segment[1..], false),
+                b'*' => (&segment[1..], true),
+                _ if segment.contains(':') || segment.contains('*') => {
+                    return Err(format!(
+                        "`{}` has a capture that isn't a whole segment\n\
+                         note: captures are written like `/:name`",
+                        segment
+                    ));
+                }
+                _ => continue,
+            };
+
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(format!("`{}` doesn't have a valid capture name", segment));
+            }
+
+            if wildcard && !last {
+                return Err(format!(
+                    "wildcard `{}` must be the last segment of `{}`",
+                    segment, path
+                ));
+            }
+
+            if captures.iter().any(|capture| capture.name == name) {
+                return Err(format!(
+                    "`{}` is captured more than once in `{}`",
+                    name, path
+                ));
+            }
+
+            captures.push(Capture {
+                name: name.to_owned(),
+            });
+        }
+
+        Ok(captures)
+    }
+
+    fn path_extractor_check(sig: &Signature, path: &LitStr) -> syn::Result<()> {
+        let captures =
+            parse_route_path(&path.value()).map_err(|msg| syn::Error::new_spanned(path, msg))?;
+
+        for pat_type in typed_inputs(sig) {
+            let params = match path_extractor(&pat_type.ty).and_then(path_params) {
+                Some(params) => params,
+                None => continue,
+            };
+
+            if params != captures.len() {
+                let msg = format!(
+                    "`Path` extracts {} parameter{}, but `{}` has {} capture{}",
+                    params,
+                    plural(params),
+                    path.value(),
+                    captures.len(),
+                    plural(captures.len()),
+                );
+
+                return Err(syn::Error::new_spanned(&pat_type.ty, msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Type extracted by `ty` if it is a `Path` extractor.
+    /// Whether the extractor deserializes the request body, going by its name.
+    fn deserializes_body(ty: &Type) -> bool {
+        match ty {
+            Type::Path(type_path) => match type_path.path.segments.last() {
+                Some(segment) => segment.ident == "Json" || segment.ident == "Form",
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn path_extractor(ty: &Type) -> Option<&Type> {
+        let segment = match ty {
+            Type::Path(type_path) => type_path.path.segments.last()?,
+            _ => return None,
+        };
+
+        if segment.ident != "Path" {
+            return None;
+        }
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Number of path parameters `ty` is deserialized from, if it is known from the type alone.
+    fn path_params(ty: &Type) -> Option<usize> {
+        match ty {
+            Type::Tuple(type_tuple) => Some(type_tuple.elems.len()),
+            Type::Paren(type_paren) => path_params(&type_paren.elem),
+            Type::Group(type_group) => path_params(&type_group.elem),
+            Type::Path(type_path) => {
+                let ident = &type_path.path.segments.last()?.ident;
+
+                if PATH_SCALARS.iter().any(|name| ident == name) {
+                    Some(1)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn plural(n: usize) -> &'static str {
+        if n == 1 {
+            ""
+        } else {
+            "s"
+        }
+    }
+
+    /// Tokens printed the way they are usually written, like `Path<(u32, u32)>` instead of
+    /// `Path < (u32 , u32) >`.
+    fn pretty_tokens<T: ToTokens>(tokens: &T) -> String {
+        let mut out = tokens.to_token_stream().to_string();
+
+        for (from, to) in [
+            (" :: ", "::"),
+            (":: ", "::"),
+            (" < ", "<"),
+            ("< ", "<"),
+            (" <", "<"),
+            (" >", ">"),
+            (" ,", ","),
+            ("( ", "("),
+            (" )", ")"),
+            ("[ ", "["),
+            (" ]", "]"),
+            ("& ", "&"),
+        ] {
+            out = out.replace(from, to);
+        }
+
+        out
+    }
+
+    fn typed_inputs(sig: &Signature) -> impl Iterator<Item = &PatType> {
+        sig.inputs.iter().filter_map(|fn_arg| match fn_arg {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+    }
+
+    /// Explicit generic arguments for naming the handler from inside the generated check function,
+    /// where its type and const parameters are in scope. Lifetimes are left to inference.
+    fn turbofish(generics: &Generics) -> proc_macro2::TokenStream {
+        let params: Vec<&Ident> = generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                GenericParam::Const(param) => Some(&param.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect();
+
+        if params.is_empty() {
+            quote!()
+        } else {
+            quote!(::<#(#params),*>)
+        }
+    }
+
+    fn uses_self(function: &ItemFn) -> bool {
+        fn contains_self(tokens: proc_macro2::TokenStream) -> bool {
+            tokens.into_iter().any(|tt| match tt {
+                TokenTree::Ident(ident) => ident == "Self",
+                TokenTree::Group(group) => contains_self(group.stream()),
+                _ => false,
+            })
+        }
+
+        contains_self(function.sig.to_token_stream())
+            || contains_self(function.block.to_token_stream())
+    }
+
+    fn check_trait_code(
+        sig: &Signature,
+        handler: &proc_macro2::TokenStream,
+        generics: &[Ident],
+    ) -> proc_macro2::TokenStream {
+        let span = sig.ident.span();
+
+        quote_spanned! {span=>
+            {
+                debug_handler(#handler);
+
+                fn debug_handler<F, Fut, #(#generics),*>(_f: F)
                 where
                     F: FnOnce(#(#generics),*) -> Fut + Clone + Send + Sync + 'static,
                     Fut: std::future::Future + Send,
@@ -298,41 +2052,243 @@ mod debug {
         }
     }
 
-    fn check_return_code(sig: &Signature, generics: &Vec<Ident>) -> proc_macro2::TokenStream {
-        let span = match &sig.output {
-            ReturnType::Default => syn::Error::new_spanned(&sig.output, "").span(),
-            ReturnType::Type(_, t) => syn::Error::new_spanned(t, "").span(),
+    fn check_return_code(
+        sig: &Signature,
+        handler: &proc_macro2::TokenStream,
+        generics: &[Ident],
+        args: &Args,
+    ) -> Vec<proc_macro2::TokenStream> {
+        let ty = match &sig.output {
+            ReturnType::Default => {
+                let span = syn::Error::new_spanned(&sig.output, "").span();
+
+                return vec![check_response_code(span, None, None, handler, generics)];
+            }
+            ReturnType::Type(_, ty) => ty,
+        };
+
+        let (ok, err) = match result_parts(ty, args) {
+            Some(parts) => parts,
+            None => {
+                let span = syn::Error::new_spanned(ty, "").span();
+                let hint = into_response_hint(ty);
+
+                return vec![check_response_code(span, hint, None, handler, generics)];
+            }
+        };
+
+        // Parts that aren't written out, like the error type of an alias, are reported on the
+        // whole type.
+        let ok_span = syn::Error::new_spanned(ok.unwrap_or(ty), "").span();
+        let ok_hint = ok.and_then(into_response_hint);
+        let err_span = syn::Error::new_spanned(err.unwrap_or(ty), "").span();
+        let err_hint = into_response_hint(err.unwrap_or(ty));
+
+        vec![
+            check_response_code(ok_span, ok_hint, Some("Ok"), handler, generics),
+            check_response_code(err_span, err_hint, Some("Err"), handler, generics),
+        ]
+    }
+
+    /// Checks that the handler's output implements `IntoResponse`, or only its `Ok` or `Err` type
+    /// if `part` is given.
+    fn check_response_code(
+        span: Span,
+        hint: Option<&str>,
+        part: Option<&str>,
+        handler: &proc_macro2::TokenStream,
+        generics: &[Ident],
+    ) -> proc_macro2::TokenStream {
+        let (result_parts, res) = match part {
+            Some(part) => {
+                let part = Ident::new(part, span);
+                let result_parts = quote_spanned! {span=>
+                    trait ResultParts {
+                        type Ok;
+                        type Err;
+                    }
+
+                    impl<T, E> ResultParts for std::result::Result<T, E> {
+                        type Ok = T;
+                        type Err = E;
+                    }
+                };
+
+                (
+                    result_parts,
+                    quote_spanned!(span=> <Res as ResultParts>::#part),
+                )
+            }
+            None => (quote!(), quote_spanned!(span=> Res)),
+        };
+        let res_bound = if part.is_some() {
+            quote_spanned!(span=> Res: ResultParts,)
+        } else {
+            quote!()
+        };
+
+        // Return types that are known mistakes are checked through a trait carrying a suggestion,
+        // which is shown when the bound isn't met.
+        let bound = match hint {
+            Some(hint) => quote_spanned! {span=>
+                #[diagnostic::on_unimplemented(
+                    message = "the trait bound `{Self}: IntoResponse` is not satisfied",
+                    label = "the trait `IntoResponse` is not implemented for `{Self}`",
+                    note = #hint
+                )]
+                trait IntoResponse {}
+
+                impl<T: axum::response::IntoResponse> IntoResponse for T {}
+            },
+            None => quote_spanned! {span=>
+                use axum::response::IntoResponse;
+            },
         };
-        let ident = &sig.ident;
 
         quote_spanned! {span=>
             {
-                debug_handler(#ident);
+                debug_handler(#handler);
+
+                #result_parts
+                #bound
 
                 fn debug_handler<F, Fut, Res, #(#generics),*>(_f: F)
                 where
                     F: FnOnce(#(#generics),*) -> Fut,
                     Fut: std::future::Future<Output = Res>,
-                    Res: axum::response::IntoResponse,
+                    #res_bound
+                    #res: IntoResponse,
                 {}
             }
         }
     }
 
-    fn check_params_code(sig: &Signature, generics: &Vec<Ident>) -> Vec<proc_macro2::TokenStream> {
-        let mut vec = Vec::new();
+    /// `Ok` and `Err` types of `ty` if it is a `Result` or a registered alias of one. Types that
+    /// aren't written out are `None`.
+    fn result_parts<'a>(ty: &'a Type, args: &Args) -> Option<(Option<&'a Type>, Option<&'a Type>)> {
+        let segment = match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last()?,
+            Type::Paren(type_paren) => return result_parts(&type_paren.elem, args),
+            Type::Group(type_group) => return result_parts(&type_group.elem, args),
+            _ => return None,
+        };
 
-        let ident = &sig.ident;
+        if segment.ident != "Result" && !args.result.contains(&segment.ident) {
+            return None;
+        }
+
+        let types: Vec<&Type> = match &segment.arguments {
+            PathArguments::AngleBracketed(arguments) => arguments
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
 
-        for (i, generic) in generics.iter().enumerate() {
-            let span = match &sig.inputs[i] {
-                FnArg::Typed(pat_type) => syn::Error::new_spanned(&pat_type.ty, "").span(),
-                _ => panic!("not a handler"),
+        Some((types.first().copied(), types.get(1).copied()))
+    }
+
+    /// Suggestion for return types that are common mistakes, because they don't implement
+    /// `IntoResponse`.
+    fn into_response_hint(ty: &Type) -> Option<&'static str> {
+        const STATUS: &str = "use `StatusCode` to respond with a status, or `(StatusCode, T)` to \
+                              respond with a status and a body";
+        const NUMBER: &str = "numbers aren't responses, convert them with `.to_string()` or use \
+                              `StatusCode` to respond with a status";
+        const OPTION: &str =
+            "return `Result<T, StatusCode>` instead and turn `None` into an error \
+                              with `.ok_or(StatusCode::NOT_FOUND)`";
+        const ERROR: &str = "wrap the error in your own type and implement `IntoResponse` for it, \
+                             for example by responding with `StatusCode::INTERNAL_SERVER_ERROR`";
+        const FUNCTION: &str = "handlers must return a response, not a function, call it and \
+                                return the result instead";
+
+        const NUMBERS: &[&str] = &[
+            "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+            "f32", "f64",
+        ];
+
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let path = &type_path.path;
+                let segment = path.segments.last()?;
+                let ident = &segment.ident;
+                let from_anyhow = path.segments.len() > 1 && path.segments[0].ident == "anyhow";
+
+                if ident == "bool" {
+                    Some(STATUS)
+                } else if NUMBERS.iter().any(|name| ident == name) {
+                    Some(NUMBER)
+                } else if ident == "Option" {
+                    Some(OPTION)
+                } else if (ident == "Error" || ident == "Result") && from_anyhow {
+                    Some(ERROR)
+                } else if ident == "Box" {
+                    let arguments = match &segment.arguments {
+                        PathArguments::AngleBracketed(arguments) => arguments,
+                        _ => return None,
+                    };
+
+                    arguments.args.iter().find_map(|arg| match arg {
+                        GenericArgument::Type(ty) => into_response_hint(ty),
+                        _ => None,
+                    })
+                } else {
+                    None
+                }
+            }
+            Type::TraitObject(type_trait_object) => {
+                trait_bounds_hint(&type_trait_object.bounds, ERROR, FUNCTION)
+            }
+            Type::ImplTrait(type_impl_trait) => {
+                trait_bounds_hint(&type_impl_trait.bounds, ERROR, FUNCTION)
+            }
+            Type::BareFn(_) => Some(FUNCTION),
+            Type::Paren(type_paren) => into_response_hint(&type_paren.elem),
+            Type::Group(type_group) => into_response_hint(&type_group.elem),
+            _ => None,
+        }
+    }
+
+    /// Suggestion for `dyn` and `impl` types bounded by `Error` or a closure trait.
+    fn trait_bounds_hint(
+        bounds: &Punctuated<TypeParamBound, Token![+]>,
+        error: &'static str,
+        function: &'static str,
+    ) -> Option<&'static str> {
+        bounds.iter().find_map(|bound| {
+            let ident = match bound {
+                TypeParamBound::Trait(trait_bound) => &trait_bound.path.segments.last()?.ident,
+                TypeParamBound::Lifetime(_) => return None,
             };
 
+            if ident == "Error" {
+                Some(error)
+            } else if ident == "Fn" || ident == "FnMut" || ident == "FnOnce" {
+                Some(function)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn check_params_code(
+        sig: &Signature,
+        handler: &proc_macro2::TokenStream,
+        generics: &[Ident],
+    ) -> Vec<proc_macro2::TokenStream> {
+        let mut vec = Vec::new();
+
+        for (pat_type, generic) in typed_inputs(sig).zip(generics) {
+            let span = syn::Error::new_spanned(&pat_type.ty, "").span();
+
             let token_stream = quote_spanned! {span=>
                 {
-                    debug_handler(#ident);
+                    debug_handler(#handler);
 
                     fn debug_handler<F, Fut, #(#generics),*>(_f: F)
                     where
@@ -348,4 +2304,187 @@ mod debug {
 
         vec
     }
+
+    /// Copies the handler body with a `Send` assertion after every `let` binding that is held
+    /// across an `.await`, so a non-`Send` future is reported at the binding that causes it.
+    /// Bindings that might be moved before the `.await` are skipped, since rustc doesn't count them
+    /// as held.
+    fn check_send_code(block: &Block, cfg: &proc_macro2::TokenStream) -> Block {
+        let mut block = block.clone();
+        SendBindings(cfg).visit_block_mut(&mut block);
+        block
+    }
+
+    struct SendBindings<'a>(&'a proc_macro2::TokenStream);
+
+    impl VisitMut for SendBindings<'_> {
+        fn visit_block_mut(&mut self, block: &mut Block) {
+            visit_mut::visit_block_mut(self, block);
+
+            let mut stmts = Vec::with_capacity(block.stmts.len());
+
+            for (i, stmt) in block.stmts.iter().enumerate() {
+                stmts.push(stmt.clone());
+
+                let local = match stmt {
+                    Stmt::Local(local) if local.init.is_some() => local,
+                    _ => continue,
+                };
+
+                let rest = &block.stmts[i + 1..];
+                let await_index = match rest.iter().position(contains_await) {
+                    Some(index) => index,
+                    None => continue,
+                };
+
+                for ident in bindings(&local.pat) {
+                    // The statement with the `.await` might move the binding before it too.
+                    if rest[..=await_index].iter().any(|stmt| moves(stmt, ident)) {
+                        continue;
+                    }
+
+                    let span = ident.span();
+                    let cfg = self.0;
+
+                    // Never runs, the assertion only needs to be type checked.
+                    stmts.push(parse_quote_spanned! {span=>
+                        #cfg
+                        {
+                            fn debug_handler<T: Send>(_binding: &T) {}
+
+                            if false {
+                                debug_handler(&#ident);
+                            }
+                        }
+                    });
+                }
+            }
+
+            block.stmts = stmts;
+        }
+
+        // Bindings inside these don't belong to the handler's future.
+        fn visit_expr_async_mut(&mut self, _: &mut ExprAsync) {}
+
+        fn visit_expr_closure_mut(&mut self, _: &mut ExprClosure) {}
+
+        fn visit_item_mut(&mut self, _: &mut Item) {}
+    }
+
+    fn bindings(pat: &Pat) -> Vec<&Ident> {
+        let mut vec = Vec::new();
+
+        match pat {
+            Pat::Ident(pat_ident) => {
+                vec.push(&pat_ident.ident);
+
+                if let Some((_, subpat)) = &pat_ident.subpat {
+                    vec.extend(bindings(subpat));
+                }
+            }
+            Pat::Box(pat_box) => vec.extend(bindings(&pat_box.pat)),
+            Pat::Or(pat_or) => {
+                if let Some(pat) = pat_or.cases.first() {
+                    vec.extend(bindings(pat));
+                }
+            }
+            Pat::Reference(pat_reference) => vec.extend(bindings(&pat_reference.pat)),
+            Pat::Slice(pat_slice) => pat_slice
+                .elems
+                .iter()
+                .for_each(|pat| vec.extend(bindings(pat))),
+            Pat::Struct(pat_struct) => pat_struct
+                .fields
+                .iter()
+                .for_each(|field| vec.extend(bindings(&field.pat))),
+            Pat::Tuple(pat_tuple) => pat_tuple
+                .elems
+                .iter()
+                .for_each(|pat| vec.extend(bindings(pat))),
+            Pat::TupleStruct(pat_tuple_struct) => pat_tuple_struct
+                .pat
+                .elems
+                .iter()
+                .for_each(|pat| vec.extend(bindings(pat))),
+            Pat::Type(pat_type) => vec.extend(bindings(&pat_type.pat)),
+            _ => {}
+        }
+
+        vec
+    }
+
+    fn contains_await(stmt: &Stmt) -> bool {
+        struct FindAwait(bool);
+
+        impl<'ast> Visit<'ast> for FindAwait {
+            fn visit_expr_await(&mut self, _: &'ast ExprAwait) {
+                self.0 = true;
+            }
+
+            fn visit_expr_async(&mut self, _: &'ast ExprAsync) {}
+
+            fn visit_expr_closure(&mut self, _: &'ast ExprClosure) {}
+
+            fn visit_item(&mut self, _: &'ast Item) {}
+        }
+
+        let mut visitor = FindAwait(false);
+        visitor.visit_stmt(stmt);
+        visitor.0
+    }
+
+    /// Whether `stmt` might move `ident`, in which case the binding might not be held across the
+    /// `.await`. Only uses that clearly borrow it, like `&ident`, `&ident.field` or `ident[i]`,
+    /// aren't counted. Method calls, `*ident` and field accesses might move it, so bindings are
+    /// skipped rather than reported wrongly.
+    fn moves(stmt: &Stmt, ident: &Ident) -> bool {
+        struct FindMove<'a>(&'a Ident, bool);
+
+        impl FindMove<'_> {
+            fn is_binding(&self, expr: &Expr) -> bool {
+                matches!(expr, Expr::Path(path) if path.qself.is_none() && path.path.is_ident(self.0))
+            }
+
+            /// Whether `expr` is the binding or a field of it, which can be borrowed without
+            /// moving the binding.
+            fn is_place(&self, expr: &Expr) -> bool {
+                match expr {
+                    Expr::Field(field) => self.is_place(&field.base),
+                    _ => self.is_binding(expr),
+                }
+            }
+        }
+
+        impl<'ast> Visit<'ast> for FindMove<'_> {
+            fn visit_expr(&mut self, expr: &'ast Expr) {
+                match expr {
+                    Expr::Path(_) if self.is_binding(expr) => self.1 = true,
+                    Expr::Reference(reference) if self.is_place(&reference.expr) => {}
+                    Expr::Index(index) if self.is_binding(&index.expr) => {
+                        self.visit_expr(&index.index)
+                    }
+                    _ => visit::visit_expr(self, expr),
+                }
+            }
+
+            // The tokens of macros can't be told apart, so any mention might be a move.
+            fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+                if mentions(mac.tokens.clone(), self.0) {
+                    self.1 = true;
+                }
+            }
+        }
+
+        fn mentions(tokens: proc_macro2::TokenStream, ident: &Ident) -> bool {
+            tokens.into_iter().any(|token| match token {
+                TokenTree::Ident(token) => token == *ident,
+                TokenTree::Group(group) => mentions(group.stream(), ident),
+                _ => false,
+            })
+        }
+
+        let mut visitor = FindMove(ident, false);
+        visitor.visit_stmt(stmt);
+        visitor.1
+    }
 }
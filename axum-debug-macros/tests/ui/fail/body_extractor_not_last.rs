@@ -0,0 +1,6 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler(_body: String, _method: axum::http::Method) {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use axum_debug_macros::debug_response;
+
+#[debug_response]
+struct Page<T> {
+    content: T,
+}
+
+fn main() {}
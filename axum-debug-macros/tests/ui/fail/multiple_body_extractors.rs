@@ -0,0 +1,6 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler(_json: axum::extract::Json<()>, _body: String) {}
+
+fn main() {}
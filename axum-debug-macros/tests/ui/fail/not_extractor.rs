@@ -0,0 +1,8 @@
+use axum_debug_macros::debug_extractor;
+
+#[debug_extractor]
+struct User {
+    name: String,
+}
+
+fn main() {}
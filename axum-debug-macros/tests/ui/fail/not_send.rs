@@ -0,0 +1,12 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler() {
+    let not_send = std::rc::Rc::new(());
+
+    async {}.await;
+
+    drop(not_send);
+}
+
+fn main() {}
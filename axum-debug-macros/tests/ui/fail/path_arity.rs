@@ -0,0 +1,10 @@
+use axum::extract::Path;
+use axum_debug_macros::debug_handler;
+
+#[debug_handler(path = "/users/:id")]
+async fn handler(Path((_user, _team)): Path<(u32, u32)>) {}
+
+#[debug_handler(path = "/users/:id/teams/:team")]
+async fn single(Path(_user): Path<u32>) {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use axum_debug_macros::debug_path;
+
+fn main() {
+    let _ = debug_path!("/users/{id}");
+    let _ = debug_path!("/files/*path/meta");
+    let _ = debug_path!("/users/:id/:id");
+    let _ = debug_path!("users");
+}
@@ -0,0 +1,6 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler(rejections)]
+async fn handler<T>(_extractor: T) {}
+
+fn main() {}
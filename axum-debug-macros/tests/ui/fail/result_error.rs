@@ -0,0 +1,10 @@
+use axum_debug_macros::debug_handler;
+
+struct MyError;
+
+#[debug_handler]
+async fn handler() -> Result<String, MyError> {
+    Err(MyError)
+}
+
+fn main() {}
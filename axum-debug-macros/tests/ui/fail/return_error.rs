@@ -0,0 +1,13 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn anyhow() -> Result<String, anyhow::Error> {
+    Ok(String::new())
+}
+
+#[debug_handler]
+async fn boxed() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn pointer() -> fn() -> String {
+    String::new
+}
+
+#[debug_handler]
+async fn closure() -> impl Fn() -> String {
+    String::new
+}
+
+fn main() {}
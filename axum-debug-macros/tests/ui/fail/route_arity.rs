@@ -0,0 +1,8 @@
+use axum::{extract::Path, handler::get, Router};
+use axum_debug::{debug_route, RouterDebugExt};
+
+async fn handler(Path((_user, _team)): Path<(u32, u32)>) {}
+
+fn main() {
+    let _app = Router::new().debug_route(debug_route!("/users/:id", get(handler)));
+}
@@ -0,0 +1,10 @@
+use axum_debug_macros::debug_handler;
+
+struct Controller;
+
+impl Controller {
+    #[debug_handler]
+    async fn handler(self) {}
+}
+
+fn main() {}
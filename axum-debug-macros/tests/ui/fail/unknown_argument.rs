@@ -0,0 +1,6 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler(foo)]
+async fn handler() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler(a: bool) -> String {
+    format!("Can I extract a bool? {}", a)
+}
+
+fn main() {}
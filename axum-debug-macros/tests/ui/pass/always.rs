@@ -0,0 +1,8 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler(always)]
+async fn handler(a: String) -> String {
+    a
+}
+
+fn main() {}
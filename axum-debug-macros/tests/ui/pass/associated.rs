@@ -0,0 +1,21 @@
+use axum_debug_macros::debug_handler;
+
+struct Controller;
+
+impl Controller {
+    #[debug_handler]
+    async fn handler(a: String) -> String {
+        Self::greet(a)
+    }
+
+    #[debug_handler(associated)]
+    async fn index() -> &'static str {
+        "Hello, world"
+    }
+
+    fn greet(name: String) -> String {
+        format!("Hello, {}", name)
+    }
+}
+
+fn main() {}
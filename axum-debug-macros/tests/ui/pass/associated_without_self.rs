@@ -0,0 +1,21 @@
+use axum_debug_macros::debug_handler;
+
+struct Controller;
+
+impl Controller {
+    #[debug_handler]
+    async fn list() -> &'static str {
+        ""
+    }
+
+    #[debug_handler]
+    async fn show(id: String) -> String {
+        greet(id)
+    }
+}
+
+fn greet(name: String) -> String {
+    format!("Hello, {}", name)
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![deny(missing_docs)]
+
+//! Attributes on handlers are kept.
+
+use axum_debug_macros::debug_handler;
+
+/// Handler with attributes.
+#[debug_handler]
+#[allow(unused_variables)]
+pub async fn handler(unused: String) {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use axum::{extract::Extension, http::Method};
+use axum_debug_macros::debug_handler;
+
+type Text = String;
+
+#[debug_handler]
+async fn optional(_method: Method, _body: Option<String>) {}
+
+#[debug_handler(body(Text))]
+async fn registered(_state: Extension<()>, _body: Text) {}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use axum_debug_macros::{debug_extractor, debug_handler};
+
+#[debug_handler]
+#[cfg(any())]
+async fn disabled() {}
+
+#[debug_handler]
+#[cfg_attr(all(), cfg(any()))]
+async fn disabled_by_cfg_attr() {}
+
+#[debug_handler]
+#[cfg_attr(all(), allow(dead_code))]
+async fn enabled() {}
+
+#[debug_extractor]
+#[cfg(any())]
+struct Disabled;
+
+fn main() {}
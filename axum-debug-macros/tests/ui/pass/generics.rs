@@ -0,0 +1,11 @@
+use axum::extract::Extension;
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler<T>(Extension(_state): Extension<T>)
+where
+    T: Clone + Send + Sync + 'static,
+{
+}
+
+fn main() {}
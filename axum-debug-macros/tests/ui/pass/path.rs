@@ -0,0 +1,18 @@
+use axum::extract::Path;
+use axum_debug_macros::{debug_handler, debug_path};
+
+#[debug_handler(path = "/users/:id/teams/:team")]
+async fn handler(Path((_user, _team)): Path<(u32, String)>) {}
+
+#[debug_handler(path = "/users/:id")]
+async fn single(Path(_user): Path<u32>) {}
+
+// Other types can't be checked.
+#[debug_handler(path = "/files/*path")]
+async fn map(Path(_params): Path<std::collections::HashMap<String, String>>) {}
+
+fn main() {
+    let _: &str = debug_path!("/");
+    let _: &str = debug_path!("/users/:id/");
+    let _: &str = debug_path!("/files/*path");
+}
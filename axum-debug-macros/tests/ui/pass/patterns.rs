@@ -0,0 +1,13 @@
+use axum::extract::{Extension, Json};
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler(
+    Extension((a, b)): Extension<(u32, u32)>,
+    Json(mut values): Json<Vec<u32>>,
+) -> String {
+    values.push(a + b);
+    format!("{:?}", values)
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use axum_debug_macros::debug_handler;
+
+type ApiResult<T> = Result<T, String>;
+
+#[debug_handler(result(ApiResult))]
+async fn handler() -> ApiResult<String> {
+    Ok(String::from("Hello, world"))
+}
+
+#[debug_handler]
+async fn std_result() -> std::result::Result<&'static str, String> {
+    Ok("Hello, world")
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use axum_debug_macros::debug_handler;
+
+#[debug_handler]
+async fn handler(a: String) -> String {
+    let (b, _c) = (a.clone(), std::sync::Arc::new(()));
+
+    async {}.await;
+
+    b
+}
+
+fn main() {}
@@ -0,0 +1,74 @@
+use axum_debug_macros::debug_handler;
+use std::rc::Rc;
+
+fn consume<T>(_value: T) {}
+
+#[debug_handler]
+async fn moved_to_function() {
+    let not_send = Rc::new(());
+    let moved = not_send;
+    consume(moved);
+
+    async {}.await;
+}
+
+macro_rules! consume {
+    ($value:expr) => {
+        consume($value)
+    };
+}
+
+#[debug_handler]
+async fn moved_to_macro() {
+    let not_send = Rc::new(());
+    consume!(not_send);
+
+    async {}.await;
+}
+
+#[debug_handler]
+async fn consumed_by_method() {
+    let not_send = Some(Rc::new(()));
+    let _inner = not_send.into_iter().count();
+
+    async {}.await;
+}
+
+#[debug_handler]
+async fn consumed_by_unwrap() {
+    let mutex = std::sync::Mutex::new(0);
+    let guard = mutex.lock();
+    let _value = *guard.unwrap();
+
+    async {}.await;
+}
+
+#[debug_handler]
+async fn consumed_by_map() {
+    let not_send = Some(Rc::new(()));
+    let _count = not_send.map(|rc| Rc::strong_count(&rc));
+
+    async {}.await;
+}
+
+#[debug_handler]
+async fn consumed_by_ok_and_expect() {
+    let result: Result<Rc<()>, ()> = Ok(Rc::new(()));
+    let _is_ok = result.ok().is_some();
+    let option = Some(Rc::new(()));
+    let _count = Rc::strong_count(&option.expect("some"));
+
+    async {}.await;
+}
+
+#[debug_handler]
+async fn moved_before_await_in_same_statement(flag: String) {
+    let not_send = Rc::new(());
+
+    if flag.is_empty() {
+        consume(not_send);
+        async {}.await;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use axum::{
+    async_trait,
+    body::{Bytes, Empty},
+    extract::{FromRequest, RequestParts},
+    http::Response,
+    response::IntoResponse,
+};
+use axum_debug_macros::{debug_extractor, debug_response};
+use std::convert::Infallible;
+
+#[debug_extractor]
+struct User;
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for User {
+    type Rejection = &'static str;
+
+    async fn from_request(_req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        Err("not logged in")
+    }
+}
+
+#[debug_response(always)]
+enum Page {
+    Empty,
+}
+
+impl IntoResponse for Page {
+    type Body = Empty<Bytes>;
+    type BodyError = Infallible;
+
+    fn into_response(self) -> Response<Self::Body> {
+        match self {
+            Page::Empty => Response::new(Empty::new()),
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+//! Requirements checked by [`check_service`] and similar functions.
+//!
+//! Every requirement is its own trait, so each one that isn't met is reported separately with a
+//! note explaining why it is needed. They are implemented for every type meeting the requirement,
+//! which they have as a supertrait, so code bounded by them can rely on it.
+//!
+//! [`check_service`]: crate::check_service
+
+use bytes::Bytes;
+use http_body::Body;
+
+/// Services must be [`Clone`].
+#[diagnostic::on_unimplemented(
+    message = "the service `{Self}` must implement `Clone`",
+    note = "axum clones services to handle requests concurrently"
+)]
+pub trait CloneService: Clone {}
+
+impl<T: Clone> CloneService for T {}
+
+/// Services must be [`Send`].
+#[diagnostic::on_unimplemented(
+    message = "the service `{Self}` must be `Send`",
+    note = "services are moved between the threads of the runtime"
+)]
+pub trait SendService: Send {}
+
+impl<T: Send> SendService for T {}
+
+/// Services must be [`Sync`].
+#[diagnostic::on_unimplemented(
+    message = "the service `{Self}` must be `Sync`",
+    note = "services are shared between the threads of the runtime"
+)]
+pub trait SyncService: Sync {}
+
+impl<T: Sync> SyncService for T {}
+
+/// Futures returned by services must be [`Send`].
+#[diagnostic::on_unimplemented(
+    message = "the future `{Self}` must be `Send`",
+    note = "futures returned by services are spawned on a multi-threaded runtime"
+)]
+pub trait SendFuture: Send {}
+
+impl<T: Send> SendFuture for T {}
+
+/// Errors must convert into `Box<dyn Error + Send + Sync>`.
+#[diagnostic::on_unimplemented(
+    message = "the error `{Self}` must convert into `Box<dyn std::error::Error + Send + Sync>`",
+    note = "errors from services and response bodies are boxed before they reach hyper"
+)]
+pub trait IntoBoxError: Into<Box<dyn std::error::Error + Send + Sync>> {}
+
+impl<T> IntoBoxError for T where T: Into<Box<dyn std::error::Error + Send + Sync>> {}
+
+/// Response bodies must produce [`Bytes`].
+#[diagnostic::on_unimplemented(
+    message = "the response body `{Self}` must implement `http_body::Body<Data = Bytes>`",
+    note = "`axum::body::box_body` can convert other bodies into `BoxBody`"
+)]
+pub trait BytesBody: Body<Data = Bytes> {}
+
+impl<T> BytesBody for T where T: Body<Data = Bytes> {}
@@ -71,7 +71,7 @@
 //! async fn main() {
 //!     let app = Router::new().route("/", get(handler));
 //!
-//!     debug_router!(app);
+//!     let app = debug_router!(app);
 //!
 //!     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
 //!         .serve(app.into_make_service())
@@ -85,14 +85,138 @@
 //! }
 //! ```
 //!
+//! The same can be done while building the router with [`RouterDebugExt::debug`].
+//!
+//! ## Missing Extensions
+//!
+//! A handler extracting an [`Extension`] that no [`AddExtensionLayer`] provides compiles fine, but
+//! fails on every request. When the router is built inside [`debug_router`], a warning is printed
+//! for every such extension once the router is built:
+//!
+//! ```rust,no_run
+//! use axum::{extract::Extension, handler::get, AddExtensionLayer, Router};
+//! use axum_debug::debug_router;
+//! use std::sync::Arc;
+//!
+//! struct Db;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let app = debug_router!(Router::new()
+//!         .route("/", get(handler))
+//!         .layer(AddExtensionLayer::new(String::from("config"))));
+//!
+//!     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+//!         .serve(app.into_make_service())
+//!         .await
+//!         .unwrap();
+//! }
+//!
+//! async fn handler(Extension(_db): Extension<Arc<Db>>) {}
+//! ```
+//!
+//! ```text
+//! axum-debug: warning: handler `app::handler` extracts `Extension<alloc::sync::Arc<app::Db>>`, but no `AddExtensionLayer` adds `alloc::sync::Arc<app::Db>` to it
+//! ```
+//!
+//! In tests, [`inspect_router`] can be used to check the same:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn extensions() {
+//!     inspect_router!(app()).assert_extensions();
+//! }
+//! ```
+//!
+//! Only handlers and layers written inside the macro are seen, so extensions added to a router
+//! built somewhere else are reported as missing.
+//!
+//! ## Routes
+//!
+//! Routers built inside [`debug_router`] print their routes to stderr when the `AXUM_DEBUG_ROUTES`
+//! environment variable is set to `text` or `json`:
+//!
+//! ```text
+//! GET     / -> app::root
+//! POST    /users -> app::create_user
+//! GET     /api/items -> app::items
+//! ```
+//!
+//! In tests, [`DebugRouter::routes`] returns the same table so it can be compared with an expected
+//! one. Handlers are named after their functions, services after their types.
+//!
+//! Routes that can't be reached because a route or a nested router tried before them serves the
+//! same requests are printed as warnings. axum tries the routes added last first, except that
+//! `a.or(b)` tries the routes of `a` before those of `b`. [`DebugRouter::assert_no_conflicts`]
+//! checks for them in tests.
+//!
+//! ## Rejections
+//!
+//! When an extractor rejects a request, the client only gets a short message like "Failed to parse
+//! the request body as JSON". Handlers using `#[debug_handler(rejections)]` get a `debug_` wrapper
+//! recording which argument rejected the request and why, and [`ExplainRejectionsLayer`] puts that
+//! into the response:
+//!
+//! ```text
+//! argument 1 of handler `app::create_user` rejected the request
+//! extractor: axum::extract::Json<app::User>
+//! rejection: axum::extract::rejection::JsonRejection
+//! details: InvalidJsonBody(Error("missing field `name`", line: 1, column: 2))
+//! ```
+//!
+//! When a `Json`, `Query` or `Form` extractor fails on a field, the path of the field is given too,
+//! like `field: user.address.zip`, since `serde` errors only name the last field.
+//!
+//! See [`ExplainRejectionsLayer`] for an example.
+//!
+//! ## Tracing
+//!
+//! Handlers using `#[debug_handler(trace)]` get a `debug_` wrapper running each of their extractors
+//! and their body in separate [`tracing`] spans, recording how long each took and whether the
+//! extractor rejected the request. See the [`trace`] module for the spans and their fields.
+//!
+//! ## Logging
+//!
+//! [`DebugLayer`] logs every request and response to stderr, including headers, latency and the
+//! start of the bodies:
+//!
+//! ```text
+//! --> POST /users (route /users)
+//!     content-type: application/json
+//! --> POST /users body: {"name":"ferris"}
+//! <-- 201 Created POST /users in 1.32ms
+//!     content-type: application/json
+//! <-- POST /users body: {"id":1,"name":"ferris"}
+//! ```
+//!
+//! Give it the [`RouteTable`] returned by [`inspect_router`] to log which route serves each
+//! request.
+//!
 //! ## Performance
 //!
-//! Macros in this crate have no effect when using release profile. (eg. `cargo build --release`)
+//! Checks are done at compile time, so they have no runtime cost, and are skipped when using
+//! release profile. (eg. `cargo build --release`) To keep them in release builds, enable the
+//! `always` feature or use `#[debug_handler(always)]`.
+//!
+//! Some macros still have effects in release builds:
+//!
+//! - [`debug_router`] boxes the router. Recording its routes and printing warnings only happens
+//!   in debug builds.
+//! - [`inspect_router`] records the routes of the router while it is built.
+//! - The `debug_` wrappers added by `#[debug_handler(rejections)]` and `#[debug_handler(trace)]`
+//!   explain rejections and trace requests while handling them.
+//!
+//! [`DebugLayer`] also works while handling requests, and passes them through untouched in release
+//! builds unless turned on with [`DebugLayer::enabled`].
 //!
 //! [`axum`]: axum
 //! [`Handler`]: axum::handler::Handler
 //! [`debug_handler`]: debug_handler
 //! [`debug_router`]: debug_router
+//! [`inspect_router`]: inspect_router
+//! [`Extension`]: axum::extract::Extension
+//! [`AddExtensionLayer`]: axum::AddExtensionLayer
+//! [`tracing`]: tracing
 
 #![warn(
     clippy::all,
@@ -108,19 +232,65 @@
 #![deny(unreachable_pub, private_in_public)]
 #![forbid(unsafe_code)]
 
+use crate::bounds::{BytesBody, CloneService, IntoBoxError, SendFuture, SendService, SyncService};
+use axum::{
+    extract::{connect_info::Connected, FromRequest},
+    response::IntoResponse,
+    routing::{BoxRoute, Route},
+    Router,
+};
 use bytes::Bytes;
 use http::{Request, Response};
 use http_body::Body;
+use hyper::server::conn::AddrStream;
+use std::future::Future;
+use tower_layer::Layer;
 use tower_service::Service;
 
+pub mod bounds;
+pub mod logging;
+pub mod path;
+pub mod rejection;
+pub mod router;
+pub mod trace;
+
+// Lets macros refer to this crate as `axum_debug` in its own tests.
+#[cfg(test)]
+extern crate self as axum_debug;
+
 #[doc(hidden)]
 pub use axum_debug_macros;
 
-pub use crate::axum_debug_macros::{debug_handler, debug_router};
+pub use crate::axum_debug_macros::{
+    debug_extractor, debug_handler, debug_path, debug_path_params, debug_response, debug_route,
+    debug_router, inspect_router,
+};
+pub use crate::logging::DebugLayer;
+pub use crate::path::{DebugRoute, PathParams};
+pub use crate::rejection::{ExplainRejectionsLayer, RejectionInfo};
+pub use crate::router::{
+    DebugRouter, MethodInfo, MissingExtension, RouteConflict, RouteInfo, RouteTable,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::path::{
+        check_arg, probe, route, Captures, OtherArg, PathArg, ProbeHandler, ProbeService,
+    };
+    pub use crate::rejection::{buffer_body, explain_rejection, rejected, FieldPath, NoFieldPath};
+    pub use crate::router::{
+        inspect, record_extension, record_handler, record_nest, record_nest_end, record_or,
+        record_or_else, record_or_end, record_route, record_service, RecordHandler, RecordService,
+        RouterInfo,
+    };
+    pub use crate::trace::{trace_extractor, trace_handler};
+    pub use tracing;
+}
 
 /// Checks if provided service can be used with [`Router`].
 ///
-/// This function is useful when debugging a [`Service`].
+/// This function is useful when debugging a [`Service`]. Every requirement that isn't met is
+/// reported separately, see [`bounds`] for the list.
 ///
 /// # Example
 /// ```rust,compile_fail
@@ -136,7 +306,7 @@ pub use crate::axum_debug_macros::{debug_handler, debug_router};
 ///
 ///     let app = Router::new().route("/", service);
 ///
-///     debug_router!(app);
+///     let app = debug_router!(app);
 ///
 ///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
 ///         .serve(app.into_make_service())
@@ -151,23 +321,26 @@ pub use crate::axum_debug_macros::{debug_handler, debug_router};
 /// ```
 ///
 /// ```text
-/// error[E0277]: the trait bound `BoxService<Request<_>, Response<...>, Infallible>: Clone` is not satisfied
+/// error[E0277]: the service `BoxService<Request<_>, Response<...>, Infallible>` must implement `Clone`
 ///    --> main.rs:9:19
 ///     |
 /// 9   |     check_service(&service);
 ///     |                   ^^^^^^^^ the trait `Clone` is not implemented for `BoxService<Request<_>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
 /// ```
 ///
 /// [`Router`]: axum::Router
 /// [`Service`]: tower_service::Service
 pub fn check_service<S, ReqBody, ResBody>(_service: &S)
 where
-    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + Sync + 'static,
-    S::Error: Into<Box<dyn std::error::Error + Send + Sync>> + Send,
-    S::Future: Send,
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S: CloneService + SendService + SyncService + 'static,
+    S::Error: IntoBoxError + Send,
+    S::Future: SendFuture,
     ReqBody: Send + 'static,
-    ResBody: Body<Data = Bytes> + Send + Sync + 'static,
-    ResBody::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    ResBody: Body + BytesBody + Send + Sync + 'static,
+    ResBody::Error: IntoBoxError,
 {
 }
 
@@ -187,7 +360,7 @@ where
 ///
 ///     let app = Router::new().route("/", debug_service(service));
 ///
-///     debug_router!(app);
+///     let app = debug_router!(app);
 ///
 ///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
 ///         .serve(app.into_make_service())
@@ -202,33 +375,1037 @@ where
 /// ```
 ///
 /// ```text
-/// error[E0277]: the trait bound `BoxService<Request<_>, Response<...>, Infallible>: Clone` is not satisfied
+/// error[E0277]: the service `BoxService<Request<_>, Response<...>, Infallible>` must implement `Clone`
 ///    --> main.rs:9:54
 ///     |
 /// 9   |     let app = Router::new().route("/", debug_service(service));
 ///     |                                                      ^^^^^^^ the trait `Clone` is not implemented for
 ///                                                                    `BoxService<Request<_>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
 /// ```
 ///
 /// [`Router`]: axum::Router
 /// [`Service`]: tower_service::Service
 pub fn debug_service<S, ReqBody, ResBody>(service: S) -> S
 where
-    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + Sync + 'static,
-    S::Error: Into<Box<dyn std::error::Error + Send + Sync>> + Send,
-    S::Future: Send,
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S: CloneService + SendService + SyncService + 'static,
+    S::Error: IntoBoxError + Send,
+    S::Future: SendFuture,
     ReqBody: Send + 'static,
-    ResBody: Body<Data = Bytes> + Send + Sync + 'static,
-    ResBody::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    ResBody: Body + BytesBody + Send + Sync + 'static,
+    ResBody::Error: IntoBoxError,
 {
     check_service(&service);
 
     service
 }
 
+/// Checks if provided layer can be used with [`Router::layer`].
+///
+/// The layer is applied to a [`BoxRoute`], standing in for the routes of a [`Router`], and the
+/// resulting service is checked the same way as in [`check_service`].
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{check_layer, debug_handler, debug_router};
+/// use tower::util::BoxService;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let layer = BoxService::layer();
+///
+///     check_layer(&layer);
+///
+///     let app = Router::new().route("/", get(handler)).layer(layer);
+///
+///     let app = debug_router!(app);
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+///
+/// #[debug_handler]
+/// async fn handler() -> &'static str {
+///     "Hello, world!"
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the service `BoxService<Request<Body>, Response<...>, Infallible>` must implement `Clone`
+///    --> main.rs:9:17
+///     |
+/// 9   |     check_layer(&layer);
+///     |                 ^^^^^^ the trait `Clone` is not implemented for `BoxService<Request<Body>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
+/// ```
+///
+/// [`Router::layer`]: axum::Router::layer
+/// [`BoxRoute`]: axum::routing::BoxRoute
+/// [`Router`]: axum::Router
+pub fn check_layer<L, ResBody>(_layer: &L)
+where
+    L: Layer<BoxRoute>,
+    L::Service: Service<Request<axum::body::Body>, Response = Response<ResBody>>,
+    L::Service: CloneService + SendService + SyncService + 'static,
+    <L::Service as Service<Request<axum::body::Body>>>::Error: IntoBoxError + Send,
+    <L::Service as Service<Request<axum::body::Body>>>::Future: SendFuture,
+    ResBody: Body + BytesBody + Send + Sync + 'static,
+    ResBody::Error: IntoBoxError,
+{
+}
+
+/// Checks and returns if provided layer can be used with [`Router::layer`].
+///
+/// See [`check_layer`] for the checks done.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{debug_handler, debug_layer, debug_router};
+/// use tower::util::BoxService;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let app = Router::new()
+///         .route("/", get(handler))
+///         .layer(debug_layer(BoxService::layer()));
+///
+///     let app = debug_router!(app);
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+///
+/// #[debug_handler]
+/// async fn handler() -> &'static str {
+///     "Hello, world!"
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the service `BoxService<Request<Body>, Response<...>, Infallible>` must implement `Clone`
+///    --> main.rs:9:28
+///     |
+/// 9   |         .layer(debug_layer(BoxService::layer()));
+///     |                            ^^^^^^^^^^^^^^^^^^^ the trait `Clone` is not implemented for
+///                                                    `BoxService<Request<Body>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
+/// ```
+///
+/// [`Router::layer`]: axum::Router::layer
+pub fn debug_layer<L, ResBody>(layer: L) -> L
+where
+    L: Layer<BoxRoute>,
+    L::Service: Service<Request<axum::body::Body>, Response = Response<ResBody>>,
+    L::Service: CloneService + SendService + SyncService + 'static,
+    <L::Service as Service<Request<axum::body::Body>>>::Error: IntoBoxError + Send,
+    <L::Service as Service<Request<axum::body::Body>>>::Future: SendFuture,
+    ResBody: Body + BytesBody + Send + Sync + 'static,
+    ResBody::Error: IntoBoxError,
+{
+    check_layer(&layer);
+
+    layer
+}
+
+/// Checks if provided make service can be passed to [`Server::serve`].
+///
+/// This function is useful when debugging errors at `.serve(app.into_make_service())`. Both the
+/// make service and the services it makes are checked.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{check_make_service, debug_handler};
+/// use tower::util::BoxService;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let service = BoxService::new(get(handler));
+///     let make_service = Router::new().route("/", service).into_make_service();
+///
+///     check_make_service(&make_service);
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(make_service)
+///         .await
+///         .unwrap();
+/// }
+///
+/// #[debug_handler]
+/// async fn handler() -> &'static str {
+///     "Hello, world!"
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the service `BoxService<Request<Body>, Response<...>, Infallible>` must implement `Clone`
+///    --> main.rs:10:5
+///     |
+/// 10  |     check_make_service(&make_service);
+///     |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ the trait `Clone` is not implemented for `BoxService<Request<Body>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
+/// ```
+///
+/// [`Server::serve`]: hyper::server::Builder::serve
+pub fn check_make_service<M, S, ResBody>(_make_service: &M)
+where
+    M: for<'a> Service<&'a AddrStream, Response = S>,
+    for<'a> <M as Service<&'a AddrStream>>::Error: IntoBoxError,
+    for<'a> <M as Service<&'a AddrStream>>::Future: SendFuture + 'static,
+    S: Service<Request<hyper::Body>, Response = Response<ResBody>>,
+    S: CloneService + SendService + 'static,
+    S::Error: IntoBoxError,
+    S::Future: SendFuture + 'static,
+    ResBody: Body + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: IntoBoxError,
+{
+}
+
+/// Checks and returns if provided make service can be passed to [`Server::serve`].
+///
+/// See [`check_make_service`] for the checks done.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{debug_handler, debug_make_service};
+/// use tower::util::BoxService;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let service = BoxService::new(get(handler));
+///     let app = Router::new().route("/", service);
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(debug_make_service(app.into_make_service()))
+///         .await
+///         .unwrap();
+/// }
+///
+/// #[debug_handler]
+/// async fn handler() -> &'static str {
+///     "Hello, world!"
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the service `BoxService<Request<Body>, Response<...>, Infallible>` must implement `Clone`
+///    --> main.rs:12:16
+///     |
+/// 12  |         .serve(debug_make_service(app.into_make_service()))
+///     |                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ the trait `Clone` is not implemented for
+///                                                                `BoxService<Request<Body>, Response<...>, Infallible>`
+///     |
+///     = note: axum clones services to handle requests concurrently
+/// ```
+///
+/// [`Server::serve`]: hyper::server::Builder::serve
+pub fn debug_make_service<M, S, ResBody>(make_service: M) -> M
+where
+    M: for<'a> Service<&'a AddrStream, Response = S>,
+    for<'a> <M as Service<&'a AddrStream>>::Error: IntoBoxError,
+    for<'a> <M as Service<&'a AddrStream>>::Future: SendFuture + 'static,
+    S: Service<Request<hyper::Body>, Response = Response<ResBody>>,
+    S: CloneService + SendService + 'static,
+    S::Error: IntoBoxError,
+    S::Future: SendFuture + 'static,
+    ResBody: Body + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: IntoBoxError,
+{
+    check_make_service(&make_service);
+
+    make_service
+}
+
+/// Checks if provided type can be used with `into_make_service_with_connect_info`.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum_debug::check_connect_info;
+/// use std::net::IpAddr;
+///
+/// fn main() {
+///     check_connect_info::<IpAddr>();
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `for<'a> IpAddr: Connected<&'a AddrStream>` is not satisfied
+///    --> main.rs:5:26
+///     |
+/// 5   |     check_connect_info::<IpAddr>();
+///     |                          ^^^^^^ the trait `for<'a> Connected<&'a AddrStream>` is not implemented for `IpAddr`
+/// ```
+pub fn check_connect_info<C>()
+where
+    C: for<'a> Connected<&'a AddrStream>,
+{
+}
+
+/// Checks if provided type can be used as an extractor.
+///
+/// This function is useful when implementing [`FromRequest`] for your own types.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum_debug::check_extractor;
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// fn main() {
+///     check_extractor::<User>();
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `User: FromRequest` is not satisfied
+///    --> main.rs:8:23
+///     |
+/// 8   |     check_extractor::<User>();
+///     |                       ^^^^ the trait `FromRequest` is not implemented for `User`
+/// ```
+///
+/// [`FromRequest`]: axum::extract::FromRequest
+pub fn check_extractor<T>()
+where
+    T: FromRequest<axum::body::Body> + Send,
+    T::Rejection: IntoResponse,
+{
+}
+
+/// Checks if provided type can be returned from a handler.
+///
+/// This function is useful when implementing [`IntoResponse`] for your own types.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum_debug::check_response;
+///
+/// struct Page {
+///     html: String,
+/// }
+///
+/// fn main() {
+///     check_response::<Page>();
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `Page: IntoResponse` is not satisfied
+///    --> main.rs:8:22
+///     |
+/// 8   |     check_response::<Page>();
+///     |                      ^^^^ the trait `IntoResponse` is not implemented for `Page`
+/// ```
+///
+/// [`IntoResponse`]: axum::response::IntoResponse
+pub fn check_response<T>()
+where
+    T: IntoResponse,
+    T::Body: Body<Data = Bytes> + Send + Sync + 'static,
+    <T::Body as Body>::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+}
+
+/// Checks if provided function or closure can be used as a handler.
+///
+/// Does the same checks as [`debug_handler`], so it can be used where the attribute can't, like
+/// closures. Extractors are checked against [`axum::body::Body`]. Handlers turned into services,
+/// for example with `.layer()` or `into_service()`, can be checked with [`check_service`].
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{check_handler, debug_router};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let handler = |a: bool| async move { format!("Can I extract a bool? {}", a) };
+///
+///     check_handler(&handler);
+///
+///     let app = debug_router!(Router::new().route("/", get(handler)));
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `bool: FromRequest` is not satisfied
+///    --> main.rs:9:5
+///     |
+/// 9   |     check_handler(&handler);
+///     |     ^^^^^^^^^^^^^ the trait `FromRequest` is not implemented for `bool`
+///     |
+///     = note: required because of the requirements on the impl of `Extractors` for `(bool,)`
+/// ```
+pub fn check_handler<T, H>(_handler: &H)
+where
+    H: HandlerFn<T> + Clone + Send + Sync + 'static,
+    H::Future: Future + Send,
+    <H::Future as Future>::Output: IntoResponse,
+    T: Extractors,
+{
+}
+
+/// Checks and returns if provided function or closure can be used as a handler.
+///
+/// See [`check_handler`] for the checks done.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{debug_handler_fn, debug_router};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let app = Router::new().route(
+///         "/",
+///         get(debug_handler_fn(|| async { false })),
+///     );
+///
+///     let app = debug_router!(app);
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// ```
+///
+/// ```text
+/// error[E0277]: the trait bound `bool: IntoResponse` is not satisfied
+///    --> main.rs:9:30
+///     |
+/// 9   |         get(debug_handler_fn(|| async { false })),
+///     |             ---------------- ^^^^^^^^^^^^^^^^^^^ the trait `IntoResponse` is not implemented for `bool`
+///     |             |
+///     |             required by a bound introduced by this call
+/// ```
+pub fn debug_handler_fn<T, H>(handler: H) -> H
+where
+    H: HandlerFn<T> + Clone + Send + Sync + 'static,
+    H::Future: Future + Send,
+    <H::Future as Future>::Output: IntoResponse,
+    T: Extractors,
+{
+    check_handler(&handler);
+
+    handler
+}
+
+/// Functions and closures taking the types in `T` as arguments.
+///
+/// Used by [`check_handler`] to find out the arguments and future of a handler.
+pub trait HandlerFn<T> {
+    /// Future returned by the function.
+    type Future;
+}
+
+/// Tuples of extractors that can be used together in a handler.
+///
+/// Used by [`check_handler`] to check every argument of a handler.
+pub trait Extractors {}
+
+macro_rules! impl_handler_fn {
+    ($($ty:ident),*) => {
+        impl<F, Fut, $($ty,)*> HandlerFn<($($ty,)*)> for F
+        where
+            F: FnOnce($($ty),*) -> Fut,
+        {
+            type Future = Fut;
+        }
+
+        impl<$($ty,)*> Extractors for ($($ty,)*)
+        where
+            $($ty: FromRequest<axum::body::Body> + Send,)*
+        {
+        }
+
+        impl<$($ty,)*> router::ExtractorNames for ($($ty,)*) {
+            fn names() -> Vec<&'static str> {
+                vec![$(std::any::type_name::<$ty>()),*]
+            }
+        }
+    };
+}
+
+impl_handler_fn!();
+impl_handler_fn!(T1);
+impl_handler_fn!(T1, T2);
+impl_handler_fn!(T1, T2, T3);
+impl_handler_fn!(T1, T2, T3, T4);
+impl_handler_fn!(T1, T2, T3, T4, T5);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
+
+/// Method-style equivalent of [`debug_router`] for building routers fluently.
+///
+/// # Example
+/// ```rust,compile_fail
+/// use axum::{handler::get, Router};
+/// use axum_debug::{debug_handler, RouterDebugExt};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let app = Router::new().route("/", get(handler)).debug();
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+///
+/// #[debug_handler]
+/// async fn handler() -> bool {
+///     false
+/// }
+/// ```
+pub trait RouterDebugExt<S> {
+    /// Boxes the router to shorten error messages.
+    fn debug<ReqBody, ResBody>(
+        self,
+    ) -> Router<BoxRoute<ReqBody, <S as Service<Request<ReqBody>>>::Error>>
+    where
+        S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+        S: CloneService + SendService + SyncService + 'static,
+        S::Error: IntoBoxError + Send,
+        S::Future: SendFuture,
+        ReqBody: Send + 'static,
+        ResBody: Body + BytesBody + Send + Sync + 'static,
+        ResBody::Error: IntoBoxError;
+
+    /// Adds a route checked by [`debug_route`].
+    fn debug_route<T>(self, route: DebugRoute<T>) -> Router<Route<T, S>>;
+}
+
+impl<S> RouterDebugExt<S> for Router<S> {
+    fn debug<ReqBody, ResBody>(
+        self,
+    ) -> Router<BoxRoute<ReqBody, <S as Service<Request<ReqBody>>>::Error>>
+    where
+        S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+        S: CloneService + SendService + SyncService + 'static,
+        S::Error: IntoBoxError + Send,
+        S::Future: SendFuture,
+        ReqBody: Send + 'static,
+        ResBody: Body + BytesBody + Send + Sync + 'static,
+        ResBody::Error: IntoBoxError,
+    {
+        self.boxed()
+    }
+
+    fn debug_route<T>(self, route: DebugRoute<T>) -> Router<Route<T, S>> {
+        self.route(route.path, route.service)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use axum_debug_macros::debug_handler;
+    use super::{
+        check_connect_info, check_extractor, check_handler, check_response, debug_handler_fn,
+        debug_layer, debug_make_service, RouterDebugExt,
+    };
+    use axum::{body::Body, extract::Extension, Router};
+    use axum_debug_macros::{
+        debug_extractor, debug_handler, debug_path, debug_path_params, debug_response, debug_route,
+        debug_router, inspect_router,
+    };
+    use http::Request;
+    use tower_service::Service;
+
+    fn _serve<S: Service<Request<Body>>>(_app: Router<S>) {}
+
+    fn _check_handler() {
+        check_handler(&|_a: String, _b: Extension<()>| async { "" });
+        let _handler = debug_handler_fn(_extractors_return);
+    }
+
+    fn _check_make_service() {
+        let make_service =
+            hyper::service::make_service_fn(|_conn: &hyper::server::conn::AddrStream| async {
+                let service = hyper::service::service_fn(|_req| async {
+                    Ok::<_, std::convert::Infallible>(http::Response::new(hyper::Body::empty()))
+                });
+
+                Ok::<_, std::convert::Infallible>(service)
+            });
+
+        let _make_service = debug_make_service(make_service);
+        check_connect_info::<std::net::SocketAddr>();
+    }
+
+    fn _check_types() {
+        check_extractor::<Extension<()>>();
+        check_response::<&'static str>();
+    }
+
+    #[debug_extractor]
+    struct _Extractor;
+
+    #[axum::async_trait]
+    impl<B: Send> axum::extract::FromRequest<B> for _Extractor {
+        type Rejection = ();
+
+        async fn from_request(
+            _req: &mut axum::extract::RequestParts<B>,
+        ) -> Result<Self, Self::Rejection> {
+            Ok(_Extractor)
+        }
+    }
+
+    #[debug_response]
+    struct _Response;
+
+    impl axum::response::IntoResponse for _Response {
+        type Body = axum::body::Empty<bytes::Bytes>;
+        type BodyError = std::convert::Infallible;
+
+        fn into_response(self) -> http::Response<Self::Body> {
+            http::Response::new(axum::body::Empty::new())
+        }
+    }
+
+    #[debug_handler]
+    async fn _path(axum::extract::Path((_a, _b)): axum::extract::Path<(u32, String)>) {}
+
+    #[debug_path_params]
+    #[derive(serde::Deserialize)]
+    struct _Params {
+        #[serde(rename = "id")]
+        _id: u32,
+    }
+
+    #[debug_handler]
+    async fn _path_params(axum::extract::Path(_params): axum::extract::Path<_Params>) {}
+
+    mod _handlers {
+        pub(super) async fn _list(axum::extract::Path(_id): axum::extract::Path<u32>) {}
+    }
+
+    // Checked when the test is compiled to code, failing the build if a handler doesn't match.
+    #[test]
+    fn debug_route() {
+        use _handlers::_list;
+
+        let closure = |axum::extract::Path((_a, _b)): axum::extract::Path<(u32, u32)>| async {};
+
+        let _router = Router::new()
+            .debug_route(debug_route!("/:a/:b", axum::handler::get(_path)))
+            .debug_route(debug_route!(
+                "/users/:id",
+                axum::handler::get(_path_params).post(_empty)
+            ))
+            .debug_route(debug_route!("/traced", axum::handler::get(debug_traced)))
+            .debug_route(debug_route!("/list/:id", axum::handler::get(_list)))
+            .debug_route(debug_route!(
+                "/handlers/:id",
+                axum::handler::get(_handlers::_list)
+            ))
+            .debug_route(debug_route!("/closure/:a/:b", axum::handler::get(closure)))
+            .debug_route(debug_route!(
+                "/",
+                axum::handler::get(_Controller::_associated)
+            ))
+            .debug_route(debug_route!(
+                "/generic",
+                axum::handler::get(_generic::<u32>)
+            ));
+    }
+
+    fn _debug_layer() {
+        let _layer = debug_layer(tower_layer::Identity::new());
+    }
+
+    fn _debug_router() {
+        _serve(debug_router!(Router::new()));
+        _serve(Router::new().debug());
+
+        // Boxed in release builds too.
+        let _router: Router<axum::routing::BoxRoute> = debug_router!(Router::new());
+    }
+
+    #[test]
+    fn missing_extensions() {
+        async fn handler(Extension(_count): Extension<u32>, Extension(_name): Extension<String>) {}
+
+        let router = inspect_router!(Router::new()
+            .route("/", axum::handler::get(handler))
+            .layer(axum::AddExtensionLayer::new(0u32)));
+        let missing = router.missing_extensions();
+
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].handler().ends_with("::handler"));
+        assert_eq!(missing[0].extension(), "alloc::string::String");
+    }
+
+    #[test]
+    fn routes() {
+        async fn handler() {}
+
+        let router = inspect_router!(Router::new()
+            .route("/", axum::handler::get(handler).post(|| async {}))
+            .nest(
+                "/api",
+                Router::new().route(debug_path!("/users"), axum::handler::get(handler))
+            ));
+
+        assert_eq!(
+            router.routes().to_string(),
+            "GET     / -> axum_debug::tests::routes::handler\n\
+             POST    / -> axum_debug::tests::routes::{{closure}}\n\
+             GET     /api/users -> axum_debug::tests::routes::handler"
+        );
+        router.assert_no_conflicts();
+    }
+
+    #[test]
+    fn or_routes() {
+        async fn show() {}
+
+        async fn me() {}
+
+        let router = inspect_router!(Router::new()
+            .route("/users/:id", axum::handler::get(show))
+            .or(Router::new().route("/users/me", axum::handler::get(me))));
+
+        assert_eq!(
+            router.routes().find("/users/me").unwrap().path(),
+            "/users/:id"
+        );
+        assert_eq!(router.conflicts().len(), 1);
+    }
+
+    #[test]
+    fn layered_handlers_and_services() {
+        use axum::{handler::Handler, service::get};
+
+        async fn handler() {}
+
+        let layered = handler.layer(tower_layer::Identity::new());
+        let service = tower::service_fn(|_req: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(http::Response::new(Body::empty()))
+        });
+
+        // Neither is a function, so both are recorded as services.
+        let router = inspect_router!(Router::new()
+            .route("/layered", axum::handler::get(layered))
+            .route("/service", get(service)));
+        let routes = router.routes();
+        let methods: Vec<(&str, &str)> = routes
+            .iter()
+            .map(|route| (route.path(), route.methods()[0].method()))
+            .collect();
+
+        assert_eq!(methods, vec![("/layered", "GET"), ("/service", "GET")]);
+        assert!(routes.iter().nth(1).unwrap().methods()[0]
+            .handler()
+            .starts_with("tower::util::service_fn::ServiceFn<"));
+    }
+
+    struct _Rejecting;
+
+    #[axum::async_trait]
+    impl<B: Send> axum::extract::FromRequest<B> for _Rejecting {
+        type Rejection = &'static str;
+
+        async fn from_request(
+            _req: &mut axum::extract::RequestParts<B>,
+        ) -> Result<Self, Self::Rejection> {
+            Err("rejected")
+        }
+    }
+
+    struct _Unreached;
+
+    #[axum::async_trait]
+    impl<B: Send> axum::extract::FromRequest<B> for _Unreached {
+        type Rejection = &'static str;
+
+        async fn from_request(
+            _req: &mut axum::extract::RequestParts<B>,
+        ) -> Result<Self, Self::Rejection> {
+            panic!("extractors after a rejection shouldn't run")
+        }
+    }
+
+    #[debug_handler(rejections)]
+    async fn rejections(_a: http::Method, _b: _Rejecting, _c: _Unreached) -> &'static str {
+        ""
+    }
+
+    /// Documented.
+    #[debug_handler(rejections)]
+    #[deprecated]
+    #[allow(unused_variables)]
+    async fn deprecated_handler(_a: http::Method) {
+        let unused = ();
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn deprecated_wrapper() {
+        deprecated_handler(http::Method::GET).await;
+        debug_deprecated_handler(Request::new(Body::empty())).await;
+    }
+
+    #[tokio::test]
+    async fn explain_rejections() {
+        let service = tower::service_fn(|req: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(debug_rejections(req).await)
+        });
+        let mut service = tower_layer::Layer::layer(&super::ExplainRejectionsLayer::new(), service);
+
+        let response = service.call(Request::new(Body::empty())).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            "argument 2 of handler `axum_debug::tests::rejections` rejected the request\n\
+             extractor: axum_debug::tests::_Rejecting\n\
+             rejection: &str\n\
+             details: \"rejected\""
+        );
+    }
+
+    #[derive(serde::Deserialize)]
+    struct _User {
+        _address: _Address,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct _Address {
+        _zip: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct _Page {
+        _page: u32,
+    }
+
+    #[debug_handler(rejections)]
+    async fn create_user(_user: axum::extract::Json<_User>) {}
+
+    #[debug_handler(rejections)]
+    async fn list_users(_page: axum::extract::Query<_Page>) {}
+
+    #[tokio::test]
+    async fn rejected_fields() {
+        let req = Request::post("/users")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"_address":{"_zip":1}}"#))
+            .unwrap();
+        let response = debug_create_user(req).await;
+        let info = response.extensions().get::<super::RejectionInfo>().unwrap();
+        assert_eq!(info.field(), Some("_address._zip"));
+
+        let req = Request::get("/users?_page=first")
+            .body(Body::empty())
+            .unwrap();
+        let response = debug_list_users(req).await;
+        let info = response.extensions().get::<super::RejectionInfo>().unwrap();
+        assert_eq!(info.field(), Some("_page"));
+    }
+
+    #[debug_handler(trace)]
+    async fn traced(_a: _Extractor) -> &'static str {
+        "traced"
+    }
+
+    #[debug_handler(trace, rejections)]
+    async fn traced_rejections(_a: _Extractor, _b: _Rejecting) {}
+
+    mod spans {
+        use std::sync::{Arc, Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        /// Name and fields of a span.
+        type Span = (&'static str, Vec<(String, String)>);
+
+        /// Subscriber recording the names and fields of spans.
+        #[derive(Clone, Default)]
+        pub(super) struct Spans(Arc<Mutex<Vec<Span>>>);
+
+        impl Spans {
+            pub(super) fn take(&self) -> Vec<Span> {
+                std::mem::take(&mut *self.0.lock().unwrap())
+            }
+        }
+
+        impl Subscriber for Spans {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut spans = self.0.lock().unwrap();
+                let mut fields = Vec::new();
+                span.record(&mut Fields(&mut fields));
+                spans.push((span.metadata().name(), fields));
+
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut spans = self.0.lock().unwrap();
+                let index = span.into_u64() as usize - 1;
+                values.record(&mut Fields(&mut spans[index].1));
+            }
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+        impl Visit for Fields<'_> {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.push((field.name().to_owned(), value.to_owned()));
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                // Durations differ between runs.
+                let value = if field.name() == "elapsed" {
+                    "..".to_owned()
+                } else {
+                    format!("{:?}", value)
+                };
+
+                self.0.push((field.name().to_owned(), value));
+            }
+        }
+
+        pub(super) fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+            fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn trace() {
+        let spans = spans::Spans::default();
+        let _guard = tracing::subscriber::set_default(spans.clone());
+
+        let response = debug_traced(Request::new(Body::empty())).await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            spans.take(),
+            vec![
+                ("traced", Vec::new()),
+                (
+                    "_a: _Extractor",
+                    spans::fields(&[
+                        ("argument", "1"),
+                        ("extractor", "axum_debug::tests::_Extractor"),
+                        ("elapsed", ".."),
+                        ("outcome", "ok"),
+                    ])
+                ),
+                (
+                    "body",
+                    spans::fields(&[("elapsed", ".."), ("status", "200")])
+                ),
+            ]
+        );
+
+        let response = debug_traced_rejections(Request::new(Body::empty())).await;
+        let info = response.extensions().get::<super::RejectionInfo>().unwrap();
+        assert_eq!(info.position(), 2);
+        assert_eq!(
+            spans.take(),
+            vec![
+                ("traced_rejections", Vec::new()),
+                (
+                    "_a: _Extractor",
+                    spans::fields(&[
+                        ("argument", "1"),
+                        ("extractor", "axum_debug::tests::_Extractor"),
+                        ("elapsed", ".."),
+                        ("outcome", "ok"),
+                    ])
+                ),
+                (
+                    "_b: _Rejecting",
+                    spans::fields(&[
+                        ("argument", "2"),
+                        ("extractor", "axum_debug::tests::_Rejecting"),
+                        ("elapsed", ".."),
+                        ("outcome", "rejected"),
+                        ("rejection", "&str"),
+                    ])
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn wrapped_associated_functions() {
+        let response = _Controller::debug_traced(Request::new(Body::empty())).await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let response = _Controller::debug_rejections(Request::new(Body::empty())).await;
+        let info = response.extensions().get::<super::RejectionInfo>().unwrap();
+        assert_eq!(info.handler(), "axum_debug::tests::rejections");
+    }
+
+    #[tokio::test]
+    async fn debug_layer_passes_through() {
+        let service = tower::service_fn(|req: Request<Body>| async {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            Ok::<_, std::convert::Infallible>(http::Response::new(hyper::Body::from(body)))
+        });
+        let layer = super::DebugLayer::new()
+            .enabled(true)
+            .body_limit(2)
+            .color(false);
+        let mut service = tower_layer::Layer::layer(&layer, service);
+
+        let response = service
+            .call(Request::new(Body::from("hello")))
+            .await
+            .unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        assert_eq!(&body[..], b"hello");
+    }
 
     #[debug_handler]
     async fn _empty() {}
@@ -245,4 +1422,73 @@ mod tests {
     async fn _extractors_return(_a: String) -> &'static str {
         ""
     }
+
+    #[debug_handler]
+    async fn _result(_a: String) -> Result<&'static str, String> {
+        Ok("")
+    }
+
+    #[debug_handler]
+    async fn _bindings_across_await(a: String) -> String {
+        let (b, _c) = (a.clone(), 0);
+        async {}.await;
+        b
+    }
+
+    #[debug_handler]
+    async fn _patterns(Extension((_a, _b)): Extension<(u32, u32)>, mut c: String) -> String {
+        c.push('!');
+        c
+    }
+
+    /// Doc comment.
+    #[debug_handler]
+    #[allow(unused_variables)]
+    #[must_use]
+    async fn _attributes(unused: String) {}
+
+    #[debug_handler]
+    async fn _body_extractor_last(_a: Extension<()>, _b: Option<String>) {}
+
+    type _Text = String;
+
+    #[debug_handler(body(_Text))]
+    async fn _custom_body_extractor(_a: Extension<()>, _b: _Text) {}
+
+    #[debug_handler]
+    async fn _generic<T>(_a: Extension<T>) -> &'static str
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        ""
+    }
+
+    #[debug_handler]
+    async fn _generic_inline<T: Clone + Send + Sync + 'static>(Extension(_a): Extension<T>) {}
+
+    struct _Controller;
+
+    impl _Controller {
+        #[debug_handler(associated)]
+        async fn _associated(_a: String) -> &'static str {
+            ""
+        }
+
+        #[debug_handler]
+        async fn _associated_self(_a: String) -> &'static str {
+            Self::_name()
+        }
+
+        #[debug_handler(trace)]
+        async fn traced(_a: _Extractor) -> &'static str {
+            Self::_name()
+        }
+
+        #[debug_handler(rejections, associated)]
+        async fn rejections(_a: _Rejecting) {}
+
+        fn _name() -> &'static str {
+            ""
+        }
+    }
 }
@@ -0,0 +1,560 @@
+//! Logging requests and responses with [`DebugLayer`].
+
+use crate::router::RouteTable;
+use bytes::Bytes;
+use futures_core::Stream;
+use http::{HeaderMap, Request, Response, StatusCode};
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    fmt::Write,
+    future::Future,
+    io::IsTerminal,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that logs every request and response to stderr.
+///
+/// Logs the method, URI, headers and the start of the body of requests, the route serving them if
+/// the routes are given with [`routes`](DebugLayer::routes), and the status, latency, headers and
+/// the start of the body of responses. Output is colored if stderr is a terminal, unless the
+/// `NO_COLOR` environment variable is set.
+///
+/// In release builds the layer passes requests and responses through without logging, unless
+/// logging is turned on with [`enabled`](DebugLayer::enabled).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{handler::get, Router};
+/// use axum_debug::DebugLayer;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let app = Router::new()
+///         .route("/", get(|| async { "Hello, World!" }))
+///         .layer(DebugLayer::new().body_limit(256));
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// ```
+///
+/// Request bodies are logged once the handler has read them.
+///
+/// ```text
+/// --> GET /
+/// <-- 200 OK GET / in 0.21ms
+///     content-type: text/plain; charset=utf-8
+/// <-- GET / body: Hello, World!
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugLayer {
+    enabled: bool,
+    body_limit: usize,
+    headers: bool,
+    color: bool,
+    routes: Option<Arc<RouteTable>>,
+}
+
+impl DebugLayer {
+    /// Create a new [`DebugLayer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether requests are logged. Defaults to `true` in debug builds and `false` in release
+    /// builds.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set how many bytes of request and response bodies are logged. Defaults to 1024.
+    ///
+    /// Bodies aren't logged if this is 0.
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+
+    /// Set whether headers are logged. Defaults to `true`.
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set whether output is colored. Defaults to `true` if stderr is a terminal and the
+    /// `NO_COLOR` environment variable isn't set.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the routes of the router, to log which route serves each request.
+    ///
+    /// The routes can be recorded with [`inspect_router`](crate::inspect_router).
+    pub fn routes(mut self, routes: RouteTable) -> Self {
+        self.routes = Some(Arc::new(routes));
+        self
+    }
+}
+
+impl Default for DebugLayer {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            body_limit: 1024,
+            headers: true,
+            color: std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal(),
+            routes: None,
+        }
+    }
+}
+
+impl<S> Layer<S> for DebugLayer {
+    type Service = DebugService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DebugService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`DebugLayer`].
+#[derive(Debug, Clone)]
+pub struct DebugService<S> {
+    inner: S,
+    layer: DebugLayer,
+}
+
+impl<S, ResBody> Service<Request<axum::body::Body>> for DebugService<S>
+where
+    S: Service<Request<axum::body::Body>, Response = Response<ResBody>>,
+    ResBody: Body<Data = Bytes>,
+{
+    type Response = Response<DebugBody<ResBody>>;
+    type Error = S::Error;
+    type Future = DebugFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<axum::body::Body>) -> Self::Future {
+        let (req, log) = if self.layer.enabled {
+            let log = RequestLog::new(&self.layer, &req);
+            log.print_request(req.headers());
+
+            let req = if log.body_limit == 0 {
+                req
+            } else {
+                let preview = Preview::new(REQUEST, log.clone());
+                req.map(|inner| {
+                    axum::body::Body::wrap_stream(RequestBody {
+                        body: DebugBody {
+                            inner,
+                            preview: Some(preview),
+                        },
+                    })
+                })
+            };
+
+            (req, Some(log))
+        } else {
+            (req, None)
+        };
+
+        DebugFuture {
+            future: self.inner.call(req),
+            log,
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`DebugService`].
+    #[derive(Debug)]
+    pub struct DebugFuture<F> {
+        #[pin]
+        future: F,
+        log: Option<RequestLog>,
+    }
+}
+
+impl<F, ResBody, E> Future for DebugFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+    ResBody: Body<Data = Bytes>,
+{
+    type Output = Result<Response<DebugBody<ResBody>>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let result = match this.future.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let log = match this.log.take() {
+            Some(log) => log,
+            None => return Poll::Ready(result.map(|res| res.map(DebugBody::passthrough))),
+        };
+
+        let response = match result {
+            Ok(response) => response,
+            Err(error) => {
+                log.print_error();
+                return Poll::Ready(Err(error));
+            }
+        };
+
+        log.print_response(response.status(), response.headers());
+
+        let preview = if log.body_limit == 0 {
+            None
+        } else {
+            Some(Preview::new(RESPONSE, log))
+        };
+
+        Poll::Ready(Ok(response.map(|inner| DebugBody { inner, preview })))
+    }
+}
+
+pin_project! {
+    /// Body of [`DebugService`], logging the start of the body once it ends or is dropped.
+    #[derive(Debug)]
+    pub struct DebugBody<B> {
+        #[pin]
+        inner: B,
+        preview: Option<Preview>,
+    }
+
+    impl<B> PinnedDrop for DebugBody<B> {
+        fn drop(this: Pin<&mut Self>) {
+            // Bodies aren't always polled to the end, like when the client disconnects.
+            if let Some(preview) = this.project().preview.take() {
+                preview.print();
+            }
+        }
+    }
+}
+
+impl<B> DebugBody<B> {
+    fn passthrough(inner: B) -> Self {
+        Self {
+            inner,
+            preview: None,
+        }
+    }
+}
+
+impl<B> Body for DebugBody<B>
+where
+    B: Body<Data = Bytes>,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        let data = match this.inner.as_mut().poll_data(cx) {
+            Poll::Ready(data) => data,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        // hyper stops polling once the body says it has ended, which can be right after the last
+        // chunk.
+        let ended = match &data {
+            Some(Ok(chunk)) => {
+                if let Some(preview) = this.preview {
+                    preview.push(chunk);
+                }
+
+                this.inner.is_end_stream()
+            }
+            Some(Err(_)) | None => true,
+        };
+
+        if ended {
+            if let Some(preview) = this.preview.take() {
+                preview.print();
+            }
+        }
+
+        Poll::Ready(data)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+pin_project! {
+    /// Request body streamed into a new `Body`, so handlers still get the body type they expect.
+    struct RequestBody<B> {
+        #[pin]
+        body: DebugBody<B>,
+    }
+}
+
+impl<B> Stream for RequestBody<B>
+where
+    B: Body<Data = Bytes>,
+{
+    type Item = Result<Bytes, B::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().body.poll_data(cx)
+    }
+}
+
+/// What is logged about a request until its response ends.
+#[derive(Debug, Clone)]
+struct RequestLog {
+    request: String,
+    route: Option<String>,
+    start: Instant,
+    body_limit: usize,
+    headers: bool,
+    color: bool,
+}
+
+impl RequestLog {
+    fn new<B>(layer: &DebugLayer, req: &Request<B>) -> Self {
+        let route = layer
+            .routes
+            .as_ref()
+            .and_then(|routes| routes.find(req.uri().path()))
+            .map(|route| route.path().to_owned());
+
+        Self {
+            request: format!("{} {}", req.method(), req.uri()),
+            route,
+            start: Instant::now(),
+            body_limit: layer.body_limit,
+            headers: layer.headers,
+            color: layer.color,
+        }
+    }
+
+    fn print_request(&self, headers: &HeaderMap) {
+        let mut out = format!(
+            "{} {}",
+            self.paint(DIM, REQUEST),
+            self.paint(BOLD, &self.request)
+        );
+
+        if let Some(route) = &self.route {
+            let _ = write!(out, " {}", self.paint(DIM, &format!("(route {})", route)));
+        }
+
+        self.write_headers(&mut out, headers);
+        eprintln!("{}", out);
+    }
+
+    fn print_response(&self, status: StatusCode, headers: &HeaderMap) {
+        let color = match status.as_u16() {
+            200..=299 => GREEN,
+            300..=399 => CYAN,
+            400..=499 => YELLOW,
+            _ => RED,
+        };
+
+        let mut out = format!(
+            "{} {} {} in {}",
+            self.paint(DIM, RESPONSE),
+            self.paint(color, &status.to_string()),
+            self.paint(BOLD, &self.request),
+            latency(self.start.elapsed()),
+        );
+
+        self.write_headers(&mut out, headers);
+        eprintln!("{}", out);
+    }
+
+    fn print_error(&self) {
+        eprintln!(
+            "{} {} {} in {}",
+            self.paint(DIM, RESPONSE),
+            self.paint(RED, "error"),
+            self.paint(BOLD, &self.request),
+            latency(self.start.elapsed()),
+        );
+    }
+
+    fn write_headers(&self, out: &mut String, headers: &HeaderMap) {
+        if !self.headers {
+            return;
+        }
+
+        for (name, value) in headers {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            let _ = write!(out, "\n    {}: {}", self.paint(DIM, name.as_str()), value);
+        }
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", color, text)
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const CYAN: &str = "36";
+
+const REQUEST: &str = "-->";
+const RESPONSE: &str = "<--";
+
+/// Start of a request or response body, logged once the body ends.
+#[derive(Debug)]
+struct Preview {
+    arrow: &'static str,
+    buf: Vec<u8>,
+    truncated: bool,
+    log: RequestLog,
+}
+
+impl Preview {
+    fn new(arrow: &'static str, log: RequestLog) -> Self {
+        Self {
+            arrow,
+            buf: Vec::new(),
+            truncated: false,
+            log,
+        }
+    }
+
+    fn push(&mut self, chunk: &Bytes) {
+        let room = self.log.body_limit - self.buf.len();
+
+        if chunk.len() > room {
+            self.truncated = true;
+        }
+
+        self.buf.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+
+    fn print(self) {
+        if let Some(line) = self.line() {
+            eprintln!("{}", line);
+        }
+    }
+
+    fn line(&self) -> Option<String> {
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let body = match std::str::from_utf8(&self.buf) {
+            Ok(text) => text.to_owned(),
+            // The limit can split a character.
+            Err(error) if self.truncated && error.error_len().is_none() => {
+                String::from_utf8_lossy(&self.buf[..error.valid_up_to()]).into_owned()
+            }
+            Err(_) => format!("<{} bytes of binary data>", self.buf.len()),
+        };
+
+        let truncated = if self.truncated {
+            self.log.paint(DIM, " ...")
+        } else {
+            String::new()
+        };
+
+        Some(format!(
+            "{} {} body: {}{}",
+            self.log.paint(DIM, self.arrow),
+            self.log.paint(BOLD, &self.log.request),
+            body,
+            truncated
+        ))
+    }
+}
+
+fn latency(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body::Full;
+
+    fn preview(body_limit: usize) -> Preview {
+        let layer = DebugLayer::new().body_limit(body_limit).color(false);
+        let req = Request::get("/users").body(()).unwrap();
+
+        Preview::new(RESPONSE, RequestLog::new(&layer, &req))
+    }
+
+    #[test]
+    fn preview_lines() {
+        let mut text = preview(8);
+        text.push(&Bytes::from_static(b"Hello, "));
+        text.push(&Bytes::from_static(b"World!"));
+        assert_eq!(text.line().unwrap(), "<-- GET /users body: Hello, W ...");
+
+        // Characters split by the limit are dropped.
+        let mut split = preview(1);
+        split.push(&Bytes::from("é"));
+        assert_eq!(split.line().unwrap(), "<-- GET /users body:  ...");
+
+        let mut binary = preview(8);
+        binary.push(&Bytes::from_static(&[0xff, 0xfe]));
+        assert_eq!(
+            binary.line().unwrap(),
+            "<-- GET /users body: <2 bytes of binary data>"
+        );
+
+        assert!(preview(8).line().is_none());
+
+        let mut request = Preview::new(REQUEST, preview(8).log);
+        request.push(&Bytes::from_static(b"{}"));
+        assert_eq!(request.line().unwrap(), "--> GET /users body: {}");
+    }
+
+    #[tokio::test]
+    async fn preview_printed_at_end_of_stream() {
+        let mut body = DebugBody {
+            inner: Full::new(Bytes::from_static(b"done")),
+            preview: Some(preview(8)),
+        };
+
+        let chunk = body.data().await.unwrap().unwrap();
+
+        assert_eq!(chunk, "done");
+        assert!(body.is_end_stream());
+        assert!(body.preview.is_none());
+    }
+}
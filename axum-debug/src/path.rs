@@ -0,0 +1,324 @@
+//! Parameters of `Path` extractors checked by [`debug_route`].
+//!
+//! ```rust,compile_fail
+//! use axum::{extract::Path, handler::get, Router};
+//! use axum_debug::{debug_route, RouterDebugExt};
+//!
+//! async fn handler(Path((user, team)): Path<(u32, u32)>) {}
+//!
+//! let app = Router::new().debug_route(debug_route!("/users/:id", get(handler)));
+//! ```
+//!
+//! [`debug_route`]: crate::debug_route
+
+use crate::HandlerFn;
+use axum::extract::Path;
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+};
+
+/// Parameters a `Path` extractor takes from the captures of a route.
+///
+/// Implemented for tuples, types extracted from a single capture and maps, and for `uuid::Uuid`
+/// with the `uuid` feature. Structs can implement it with [`debug_path_params`].
+///
+/// [`debug_path_params`]: crate::debug_path_params
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't describe its path parameters",
+    note = "add `#[axum_debug::debug_path_params]` to the struct to check it against routes"
+)]
+pub trait PathParams {
+    /// Number of captures the type is extracted from, `None` if any number works.
+    const COUNT: Option<usize>;
+
+    /// Names of the captures the type is extracted from, `None` if they are extracted by position.
+    const NAMES: Option<&'static [&'static str]>;
+}
+
+/// Handlers without a `Path` extractor can be mounted on any route.
+impl PathParams for () {
+    const COUNT: Option<usize> = None;
+    const NAMES: Option<&'static [&'static str]> = None;
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl PathParams for $ty {
+                const COUNT: Option<usize> = Some(1);
+                const NAMES: Option<&'static [&'static str]> = None;
+            }
+        )*
+    };
+}
+
+impl_scalar!(
+    bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, String
+);
+
+#[cfg(feature = "uuid")]
+impl_scalar!(uuid::Uuid);
+
+macro_rules! impl_tuple {
+    ($($count:literal => ($($ty:ident),*)),* $(,)?) => {
+        $(
+            impl<$($ty,)*> PathParams for ($($ty,)*) {
+                const COUNT: Option<usize> = Some($count);
+                const NAMES: Option<&'static [&'static str]> = None;
+            }
+        )*
+    };
+}
+
+impl_tuple!(
+    1 => (T1),
+    2 => (T1, T2),
+    3 => (T1, T2, T3),
+    4 => (T1, T2, T3, T4),
+    5 => (T1, T2, T3, T4, T5),
+    6 => (T1, T2, T3, T4, T5, T6),
+    7 => (T1, T2, T3, T4, T5, T6, T7),
+    8 => (T1, T2, T3, T4, T5, T6, T7, T8),
+    9 => (T1, T2, T3, T4, T5, T6, T7, T8, T9),
+    10 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10),
+    11 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11),
+    12 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12),
+    13 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13),
+    14 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14),
+    15 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15),
+    16 => (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16),
+);
+
+impl<K, V, S> PathParams for HashMap<K, V, S> {
+    const COUNT: Option<usize> = None;
+    const NAMES: Option<&'static [&'static str]> = None;
+}
+
+impl<K, V> PathParams for BTreeMap<K, V> {
+    const COUNT: Option<usize> = None;
+    const NAMES: Option<&'static [&'static str]> = None;
+}
+
+impl<T> PathParams for Vec<T> {
+    const COUNT: Option<usize> = None;
+    const NAMES: Option<&'static [&'static str]> = None;
+}
+
+/// Handler of a route checked by [`debug_route`](crate::debug_route).
+///
+/// The arguments of functions and closures are found with [`ProbeHandler::args`], since method
+/// resolution picks it before [`ProbeService::args`], which takes `&&Self`. Anything else, like a
+/// handler wrapped in layers, has no arguments to check.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Probe<'a, H>(PhantomData<&'a H>);
+
+#[doc(hidden)]
+pub fn probe<H>(_handler: &H) -> Probe<'_, H> {
+    Probe(PhantomData)
+}
+
+#[doc(hidden)]
+pub trait ProbeHandler<T> {
+    fn args(&self) -> Args<T>;
+}
+
+impl<H, T> ProbeHandler<T> for Probe<'_, H>
+where
+    H: HandlerFn<T>,
+{
+    fn args(&self) -> Args<T> {
+        Args(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbeService {
+    fn args(&self) -> Args<()>;
+}
+
+impl<H> ProbeService for &Probe<'_, H> {
+    fn args(&self) -> Args<()> {
+        Args(PhantomData)
+    }
+}
+
+/// Arguments of a handler, taken one at a time so each can be matched against `Path`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Args<T>(PhantomData<T>);
+
+impl<T: ArgList> Args<T> {
+    pub fn arg(&self) -> Arg<T::First> {
+        Arg(PhantomData)
+    }
+
+    pub fn rest(&self) -> Args<T::Rest> {
+        Args(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ArgList {
+    type First;
+    type Rest: ArgList;
+}
+
+impl ArgList for () {
+    type First = ();
+    type Rest = ();
+}
+
+macro_rules! impl_arg_list {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first, $($rest,)*> ArgList for ($first, $($rest,)*) {
+            type First = $first;
+            type Rest = ($($rest,)*);
+        }
+
+        impl_arg_list!($($rest),*);
+    };
+    () => {};
+}
+
+impl_arg_list!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
+
+/// Argument of a handler. `Path` extractors are found with [`PathArg::path`], since method
+/// resolution picks it before [`OtherArg::path`], which takes `&&Self`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Arg<T>(PhantomData<T>);
+
+#[doc(hidden)]
+pub trait PathArg<T> {
+    fn path(&self) -> PathOf<T>;
+}
+
+impl<T> PathArg<T> for Arg<Path<T>> {
+    fn path(&self) -> PathOf<T> {
+        PathOf(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait OtherArg {
+    fn path(&self) -> NotPath;
+}
+
+impl<T> OtherArg for &Arg<T> {
+    fn path(&self) -> NotPath {
+        NotPath
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct PathOf<T>(PhantomData<T>);
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct NotPath;
+
+/// Captures of the route, declared by [`debug_route`](crate::debug_route).
+#[doc(hidden)]
+pub trait Captures {
+    const NAMES: &'static [&'static str];
+}
+
+#[doc(hidden)]
+pub trait RouteArg {
+    fn check<C: Captures>(self);
+}
+
+impl<T: PathParams> RouteArg for PathOf<T> {
+    fn check<C: Captures>(self) {
+        // The handler's type is only known here, so the constant is evaluated when this is
+        // compiled to code, which `cargo check` doesn't do.
+        let () = Checked::<T, C>::OK;
+    }
+}
+
+impl RouteArg for NotPath {
+    fn check<C: Captures>(self) {}
+}
+
+#[doc(hidden)]
+pub fn check_arg<C: Captures, A: RouteArg>(arg: A) {
+    arg.check::<C>();
+}
+
+struct Checked<T, C>(PhantomData<(T, C)>);
+
+impl<T: PathParams, C: Captures> Checked<T, C> {
+    const OK: () = check_route::<T>(C::NAMES);
+}
+
+const fn check_route<T: PathParams>(captures: &[&str]) {
+    if let Some(count) = T::COUNT {
+        if count != captures.len() {
+            panic!("the number of captures in the route doesn't match the `Path` extractor");
+        }
+    }
+
+    if let Some(names) = T::NAMES {
+        let mut i = 0;
+
+        while i < names.len() {
+            if !contains(captures, names[i]) {
+                panic!("a field of the `Path` extractor isn't captured by the route");
+            }
+
+            i += 1;
+        }
+    }
+}
+
+const fn contains(list: &[&str], s: &str) -> bool {
+    let mut i = 0;
+
+    while i < list.len() {
+        if str_eq(list[i], s) {
+            return true;
+        }
+
+        i += 1;
+    }
+
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// Route checked by [`debug_route`], added to a router with [`RouterDebugExt::debug_route`].
+///
+/// [`debug_route`]: crate::debug_route
+/// [`RouterDebugExt::debug_route`]: crate::RouterDebugExt::debug_route
+#[derive(Debug, Clone)]
+pub struct DebugRoute<T> {
+    pub(crate) path: &'static str,
+    pub(crate) service: T,
+}
+
+#[doc(hidden)]
+pub fn route<T>(path: &'static str, service: T) -> DebugRoute<T> {
+    DebugRoute { path, service }
+}
@@ -0,0 +1,353 @@
+//! Explaining rejections of handlers using `#[debug_handler(rejections)]`.
+//!
+//! When an extractor rejects a request, the handler's `debug_` wrapper responds with the rejection
+//! and attaches a [`RejectionInfo`] to the response. [`ExplainRejectionsLayer`] replaces the body of such
+//! responses with the information.
+
+use axum::{
+    body::{box_body, BoxBody},
+    extract::{Form, Json, Query, RequestParts},
+    response::IntoResponse,
+    BoxError,
+};
+use bytes::Bytes;
+use http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use http_body::{Body, Full};
+use pin_project_lite::pin_project;
+use serde::{de::DeserializeOwned, Deserializer};
+use std::{
+    any::type_name,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Information about an extractor rejecting a request.
+///
+/// The `debug_` wrappers of handlers using `#[debug_handler(rejections)]` add this to the
+/// extensions of their rejection responses.
+#[derive(Debug, Clone)]
+pub struct RejectionInfo {
+    handler: &'static str,
+    position: usize,
+    extractor: &'static str,
+    rejection: &'static str,
+    field: Option<String>,
+    details: String,
+}
+
+impl RejectionInfo {
+    /// Path of the handler that rejected the request.
+    pub fn handler(&self) -> &str {
+        self.handler
+    }
+
+    /// Position of the rejecting argument in the handler, starting from 1.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Type of the rejecting extractor.
+    pub fn extractor(&self) -> &str {
+        self.extractor
+    }
+
+    /// Type of the rejection.
+    pub fn rejection(&self) -> &str {
+        self.rejection
+    }
+
+    /// Path of the field that failed to deserialize, like `user.address.zip`, if a `Json`, `Query`
+    /// or `Form` extractor rejected the request because of a field.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// [`Debug`](fmt::Debug) output of the rejection, including the error it wraps, like the
+    /// `serde` error of a `Json` rejection.
+    pub fn details(&self) -> &str {
+        &self.details
+    }
+}
+
+impl fmt::Display for RejectionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "argument {} of handler `{}` rejected the request",
+            self.position, self.handler
+        )?;
+        writeln!(f, "extractor: {}", self.extractor)?;
+        writeln!(f, "rejection: {}", self.rejection)?;
+
+        if let Some(field) = &self.field {
+            writeln!(f, "field: {}", field)?;
+        }
+
+        write!(f, "details: {}", self.details)
+    }
+}
+
+#[doc(hidden)]
+pub fn explain_rejection<T, R>(
+    handler: &'static str,
+    position: usize,
+    rejection: R,
+    field: Option<String>,
+) -> Response<BoxBody>
+where
+    R: IntoResponse + fmt::Debug,
+{
+    let info = RejectionInfo {
+        handler,
+        position,
+        extractor: type_name::<T>(),
+        rejection: type_name::<R>(),
+        field,
+        details: format!("{:?}", rejection),
+    };
+
+    let mut response = rejection.into_response().map(box_body);
+    response.extensions_mut().insert(info);
+
+    response
+}
+
+/// Buffers the request body, so the field a `Json` or `Form` extractor failed on can be found from
+/// it.
+#[doc(hidden)]
+pub async fn buffer_body(
+    req: Request<axum::body::Body>,
+) -> Result<(Request<axum::body::Body>, Bytes), Response<BoxBody>> {
+    let (parts, body) = req.into_parts();
+
+    match hyper::body::to_bytes(body).await {
+        Ok(bytes) => {
+            let req = Request::from_parts(parts, axum::body::Body::from(bytes.clone()));
+            Ok((req, bytes))
+        }
+        Err(_) => {
+            let mut response =
+                Response::new(box_body(Full::from("Failed to buffer the request body")));
+            *response.status_mut() = StatusCode::BAD_REQUEST;
+            Err(response)
+        }
+    }
+}
+
+/// Extractor whose rejection is being explained.
+///
+/// The field that failed to deserialize is found with [`FieldPath::field`] for `Json`, `Query`
+/// and `Form`, since method resolution picks it before [`NoFieldPath::field`], which takes
+/// `&&Self`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Rejected<T>(PhantomData<T>);
+
+#[doc(hidden)]
+pub fn rejected<T>() -> Rejected<T> {
+    Rejected(PhantomData)
+}
+
+#[doc(hidden)]
+pub trait FieldPath {
+    fn field(&self, req: &RequestParts<axum::body::Body>, body: Option<&Bytes>) -> Option<String>;
+}
+
+impl<T> FieldPath for Rejected<Json<T>>
+where
+    T: DeserializeOwned,
+{
+    fn field(&self, _req: &RequestParts<axum::body::Body>, body: Option<&Bytes>) -> Option<String> {
+        field_path::<T, _>(&mut serde_json::Deserializer::from_slice(body?))
+    }
+}
+
+impl<T> FieldPath for Rejected<Query<T>>
+where
+    T: DeserializeOwned,
+{
+    fn field(&self, req: &RequestParts<axum::body::Body>, _body: Option<&Bytes>) -> Option<String> {
+        let query = req.uri().query().unwrap_or_default();
+        field_path::<T, _>(urlencoded(query.as_bytes()))
+    }
+}
+
+impl<T> FieldPath for Rejected<Form<T>>
+where
+    T: DeserializeOwned,
+{
+    fn field(&self, req: &RequestParts<axum::body::Body>, body: Option<&Bytes>) -> Option<String> {
+        // Like `Form`, which reads the query string of `GET` and `HEAD` requests.
+        let input = if req.method() == Method::GET || req.method() == Method::HEAD {
+            req.uri().query().unwrap_or_default().as_bytes()
+        } else {
+            body?
+        };
+
+        field_path::<T, _>(urlencoded(input))
+    }
+}
+
+#[doc(hidden)]
+pub trait NoFieldPath {
+    fn field(
+        &self,
+        _req: &RequestParts<axum::body::Body>,
+        _body: Option<&Bytes>,
+    ) -> Option<String> {
+        None
+    }
+}
+
+impl<T> NoFieldPath for &Rejected<T> {}
+
+fn urlencoded(input: &[u8]) -> serde_urlencoded::Deserializer<'_> {
+    serde_urlencoded::Deserializer::new(form_urlencoded::parse(input))
+}
+
+/// Path of the field `T` fails to deserialize on, if the error is about a field.
+fn field_path<'de, T, D>(deserializer: D) -> Option<String>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let error = serde_path_to_error::deserialize::<_, T>(deserializer).err()?;
+    let path = error.path();
+    path.iter().next()?;
+
+    Some(path.to_string())
+}
+
+/// Layer that replaces the body of rejection responses with a detailed explanation.
+///
+/// Only responses of the `debug_` wrappers of handlers using `#[debug_handler(rejections)]` are
+/// changed, since the information is only collected there. The status code is kept.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::Json, handler::post, Router};
+/// use axum_debug::{debug_handler, ExplainRejectionsLayer};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[debug_handler(rejections)]
+/// async fn create_user(Json(user): Json<User>) -> String {
+///     user.name
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let app = Router::new()
+///         .route("/users", post(debug_create_user))
+///         .layer(ExplainRejectionsLayer::new());
+///
+///     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// ```
+///
+/// Posting `{}` to `/users` responds with `400 Bad Request` and:
+///
+/// ```text
+/// argument 1 of handler `app::create_user` rejected the request
+/// extractor: axum::extract::Json<app::User>
+/// rejection: axum::extract::rejection::JsonRejection
+/// details: InvalidJsonBody(Error("missing field `name`", line: 1, column: 2))
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExplainRejectionsLayer {
+    _priv: (),
+}
+
+impl ExplainRejectionsLayer {
+    /// Create a new [`ExplainRejectionsLayer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for ExplainRejectionsLayer {
+    type Service = ExplainRejections<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ExplainRejections { inner }
+    }
+}
+
+/// Service created by [`ExplainRejectionsLayer`].
+#[derive(Debug, Clone)]
+pub struct ExplainRejections<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ExplainRejections<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Body<Data = Bytes> + Send + Sync + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = ExplainRejectionsFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        ExplainRejectionsFuture {
+            future: self.inner.call(req),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`ExplainRejections`].
+    #[derive(Debug)]
+    pub struct ExplainRejectionsFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, ResBody, E> Future for ExplainRejectionsFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+    ResBody: Body<Data = Bytes> + Send + Sync + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Output = Result<Response<BoxBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let response = match self.project().future.poll(cx) {
+            Poll::Ready(result) => result?,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let info = match response.extensions().get::<RejectionInfo>() {
+            Some(info) => info.to_string(),
+            None => return Poll::Ready(Ok(response.map(box_body))),
+        };
+
+        let (mut parts, _) = response.into_parts();
+        parts.headers.remove(header::CONTENT_LENGTH);
+        parts.headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; charset=utf-8"),
+        );
+
+        Poll::Ready(Ok(Response::from_parts(parts, box_body(Full::from(info)))))
+    }
+}
@@ -0,0 +1,1121 @@
+//! Information about routers recorded by [`debug_router`] and [`inspect_router`].
+//!
+//! [`debug_router`]: crate::debug_router
+//! [`inspect_router`]: crate::inspect_router
+
+use crate::HandlerFn;
+use std::{
+    any::type_name,
+    cell::{Cell, RefCell},
+    fmt,
+};
+
+/// Router returned by [`inspect_router`] along with what was recorded while building it.
+///
+/// [`inspect_router`]: crate::inspect_router
+#[derive(Debug)]
+pub struct DebugRouter<R> {
+    router: R,
+    info: RouterInfo,
+}
+
+impl<R> DebugRouter<R> {
+    /// Extensions extracted by handlers that no `AddExtensionLayer` provides to them.
+    pub fn missing_extensions(&self) -> Vec<MissingExtension> {
+        self.info.missing_extensions()
+    }
+
+    /// Panics if any extension extracted by a handler is missing.
+    ///
+    /// This function is useful in tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing every missing extension, if there are any.
+    pub fn assert_extensions(&self) {
+        let missing = self.missing_extensions();
+
+        if !missing.is_empty() {
+            let list: Vec<String> = missing.iter().map(|missing| missing.to_string()).collect();
+
+            panic!("missing extensions:\n{}", list.join("\n"));
+        }
+    }
+
+    /// Routes of the router.
+    pub fn routes(&self) -> RouteTable {
+        self.info.routes()
+    }
+
+    /// Routes that shadow each other, see [`RouteTable::conflicts`].
+    pub fn conflicts(&self) -> Vec<RouteConflict> {
+        self.routes().conflicts()
+    }
+
+    /// Panics if any route shadows another one.
+    ///
+    /// This function is useful in tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing every conflict, if there are any.
+    pub fn assert_no_conflicts(&self) {
+        let conflicts = self.conflicts();
+
+        if !conflicts.is_empty() {
+            let list: Vec<String> = conflicts
+                .iter()
+                .map(|conflict| conflict.to_string())
+                .collect();
+
+            panic!("conflicting routes:\n{}", list.join("\n"));
+        }
+    }
+
+    /// Returns the inspected router.
+    pub fn into_inner(self) -> R {
+        self.router
+    }
+}
+
+/// Extension extracted by a handler but not provided to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingExtension {
+    handler: &'static str,
+    extension: String,
+}
+
+impl MissingExtension {
+    /// Name of the handler extracting the extension.
+    pub fn handler(&self) -> &str {
+        self.handler
+    }
+
+    /// Type of the missing extension.
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
+}
+
+impl fmt::Display for MissingExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "handler `{}` extracts `Extension<{}>`, but no `AddExtensionLayer` adds `{}` to it",
+            self.handler, self.extension, self.extension
+        )
+    }
+}
+
+/// Handlers and extensions in the order they were added to a router.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct RouterInfo {
+    events: RefCell<Vec<Event>>,
+}
+
+#[derive(Debug)]
+enum Event {
+    Route(String),
+    Nest(String),
+    NestEnd,
+    // `a.or(b)`, recorded before `a`, before `b` and after `b`.
+    Or,
+    OrElse,
+    OrEnd,
+    Handler {
+        method: &'static str,
+        name: &'static str,
+        extractors: Vec<&'static str>,
+    },
+    Extension(&'static str),
+}
+
+impl RouterInfo {
+    /// Prints every missing extension and conflicting route to stderr, and the route table too if
+    /// the `AXUM_DEBUG_ROUTES` environment variable is set to `text` or `json`.
+    pub fn report(&self) {
+        for missing in self.missing_extensions() {
+            eprintln!("axum-debug: warning: {}", missing);
+        }
+
+        for conflict in self.routes().conflicts() {
+            eprintln!("axum-debug: warning: {}", conflict);
+        }
+
+        match std::env::var("AXUM_DEBUG_ROUTES").as_deref() {
+            Ok("text") => eprintln!("{}", self.routes()),
+            Ok("json") => eprintln!("{}", self.routes().to_json()),
+            _ => {}
+        }
+    }
+
+    fn routes(&self) -> RouteTable {
+        let mut routes: Vec<RouteInfo> = Vec::new();
+        let mut nests: Vec<String> = Vec::new();
+        let mut prefixes: Vec<String> = Vec::new();
+        // Routers being built, innermost last.
+        let mut stack: Vec<Vec<Node>> = vec![Vec::new()];
+        // Route the next handlers are added to.
+        let mut current = None;
+
+        for event in self.events.borrow().iter() {
+            match event {
+                Event::Route(path) => {
+                    let prefix = prefixes.last().cloned().unwrap_or_default();
+
+                    routes.push(RouteInfo {
+                        path: join_paths(&prefix, path),
+                        prefix,
+                        methods: Vec::new(),
+                    });
+                    current = Some(routes.len() - 1);
+                    push_node(&mut stack, Node::Route(routes.len() - 1));
+                }
+                Event::Nest(path) => {
+                    let prefix = prefixes.last().cloned().unwrap_or_default();
+
+                    let prefix = join_paths(&prefix, path).trim_end_matches('/').to_owned();
+
+                    nests.push(prefix.clone());
+                    prefixes.push(prefix);
+                    stack.push(Vec::new());
+                    current = None;
+                }
+                Event::NestEnd => {
+                    prefixes.pop();
+                    let nodes = stack.pop().unwrap_or_default();
+                    push_node(&mut stack, Node::Nest(nests.len() - 1, nodes));
+                    current = None;
+                }
+                Event::Or | Event::OrElse => {
+                    stack.push(Vec::new());
+                    current = None;
+                }
+                Event::OrEnd => {
+                    let second = stack.pop().unwrap_or_default();
+                    let first = stack.pop().unwrap_or_default();
+                    push_node(&mut stack, Node::Or(first, second));
+                    current = None;
+                }
+                Event::Handler { method, name, .. } => {
+                    // Handlers nested directly are served at the prefix.
+                    let route = match current {
+                        Some(route) => route,
+                        None => {
+                            let prefix = prefixes.last().cloned().unwrap_or_default();
+
+                            routes.push(RouteInfo {
+                                path: join_paths(&prefix, "/"),
+                                prefix,
+                                methods: Vec::new(),
+                            });
+                            push_node(&mut stack, Node::Route(routes.len() - 1));
+                            routes.len() - 1
+                        }
+                    };
+
+                    routes[route].methods.push(MethodInfo {
+                        method,
+                        handler: name,
+                    });
+                    current = Some(route);
+                }
+                Event::Extension(_) => {}
+            }
+        }
+
+        // Routers left open by a router expression that didn't finish building.
+        while stack.len() > 1 {
+            let nodes = stack.pop().unwrap_or_default();
+            push_node(&mut stack, Node::Or(nodes, Vec::new()));
+        }
+
+        let mut order = Vec::new();
+        let mut nest_ends = vec![0; nests.len()];
+        try_order(&stack[0], &mut order, &mut nest_ends);
+
+        RouteTable {
+            routes,
+            order,
+            nests: nests.into_iter().zip(nest_ends).collect(),
+        }
+    }
+
+    fn missing_extensions(&self) -> Vec<MissingExtension> {
+        let events = self.events.borrow();
+        let mut vec = Vec::new();
+
+        for (i, event) in events.iter().enumerate() {
+            let (name, extractors) = match event {
+                Event::Handler {
+                    name, extractors, ..
+                } => (name, extractors),
+                _ => continue,
+            };
+
+            // Layers only apply to routes added before them, and layers of routers nested after
+            // the handler only apply to the routes of those routers.
+            let provided = |extension: &str| {
+                let mut depth = 0;
+                let mut outermost = 0;
+
+                events[i + 1..].iter().any(|event| {
+                    match event {
+                        Event::Nest(_) | Event::Or => depth += 1,
+                        Event::NestEnd | Event::OrEnd => {
+                            depth -= 1;
+                            outermost = outermost.min(depth);
+                        }
+                        // The second router of `or` starts where the first one ends.
+                        Event::OrElse => outermost = outermost.min(depth - 1),
+                        Event::Extension(ty) => return *ty == extension && depth == outermost,
+                        _ => {}
+                    }
+
+                    false
+                })
+            };
+
+            for extension in extractors.iter().flat_map(|ty| required_extensions(ty)) {
+                if !provided(extension) {
+                    vec.push(MissingExtension {
+                        handler: name,
+                        extension: extension.to_owned(),
+                    });
+                }
+            }
+        }
+
+        vec
+    }
+}
+
+/// Route, nested router or routers combined with `or`, in a router being built.
+#[derive(Debug)]
+enum Node {
+    Route(usize),
+    Nest(usize, Vec<Node>),
+    Or(Vec<Node>, Vec<Node>),
+}
+
+fn push_node(stack: &mut [Vec<Node>], node: Node) {
+    if let Some(nodes) = stack.last_mut() {
+        nodes.push(node);
+    }
+}
+
+/// Pushes routes in the order axum tries them, with the number of routes tried up to the end of
+/// each nested router.
+///
+/// Routes added last are tried first, but `a.or(b)` tries every route of `a` before `b`.
+fn try_order(nodes: &[Node], order: &mut Vec<usize>, nest_ends: &mut [usize]) {
+    for node in nodes.iter().rev() {
+        match node {
+            Node::Route(route) => order.push(*route),
+            Node::Nest(nest, nodes) => {
+                try_order(nodes, order, nest_ends);
+                nest_ends[*nest] = order.len();
+            }
+            Node::Or(first, second) => {
+                try_order(first, order, nest_ends);
+                try_order(second, order, nest_ends);
+            }
+        }
+    }
+}
+
+/// Types of the extractors a handler takes, as given by [`type_name`].
+#[doc(hidden)]
+pub trait ExtractorNames {
+    fn names() -> Vec<&'static str>;
+}
+
+/// Function, closure or anything else passed to a function like `get`, which could be a handler
+/// wrapped in layers or a service too.
+///
+/// Recorded with [`RecordHandler::record`] if it is a function or closure, since method resolution
+/// picks it before [`RecordService::record`], which takes `&&Self`.
+#[doc(hidden)]
+pub struct Recording<'a, H> {
+    info: &'a RouterInfo,
+    method: &'static str,
+    handler: Cell<Option<H>>,
+}
+
+impl<H> Recording<'_, H> {
+    fn take(&self) -> H {
+        self.handler.take().expect("recorded twice")
+    }
+}
+
+impl<H> fmt::Debug for Recording<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("method", &self.method)
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+pub fn record_handler<'a, H>(
+    info: &'a RouterInfo,
+    method: &'static str,
+    handler: H,
+) -> Recording<'a, H> {
+    Recording {
+        info,
+        method,
+        handler: Cell::new(Some(handler)),
+    }
+}
+
+#[doc(hidden)]
+pub trait RecordHandler<T> {
+    type Handler;
+
+    fn record(&self) -> Self::Handler;
+}
+
+impl<H, T> RecordHandler<T> for Recording<'_, H>
+where
+    H: HandlerFn<T>,
+    T: ExtractorNames,
+{
+    type Handler = H;
+
+    fn record(&self) -> H {
+        self.info.events.borrow_mut().push(Event::Handler {
+            method: self.method,
+            name: type_name::<H>(),
+            extractors: T::names(),
+        });
+
+        self.take()
+    }
+}
+
+#[doc(hidden)]
+pub trait RecordService {
+    type Service;
+
+    fn record(&self) -> Self::Service;
+}
+
+impl<H> RecordService for &Recording<'_, H> {
+    type Service = H;
+
+    fn record(&self) -> H {
+        record_service(self.info, self.method, self.take())
+    }
+}
+
+#[doc(hidden)]
+pub fn record_service<S>(info: &RouterInfo, method: &'static str, service: S) -> S {
+    info.events.borrow_mut().push(Event::Handler {
+        method,
+        name: type_name::<S>(),
+        extractors: Vec::new(),
+    });
+
+    service
+}
+
+#[doc(hidden)]
+pub fn record_route<'a>(info: &RouterInfo, path: &'a str) -> &'a str {
+    info.events.borrow_mut().push(Event::Route(path.to_owned()));
+
+    path
+}
+
+#[doc(hidden)]
+pub fn record_nest<'a>(info: &RouterInfo, path: &'a str) -> &'a str {
+    info.events.borrow_mut().push(Event::Nest(path.to_owned()));
+
+    path
+}
+
+#[doc(hidden)]
+pub fn record_nest_end<S>(info: &RouterInfo, service: S) -> S {
+    info.events.borrow_mut().push(Event::NestEnd);
+
+    service
+}
+
+#[doc(hidden)]
+pub fn record_or(info: &RouterInfo) {
+    info.events.borrow_mut().push(Event::Or);
+}
+
+#[doc(hidden)]
+pub fn record_or_else(info: &RouterInfo) {
+    info.events.borrow_mut().push(Event::OrElse);
+}
+
+#[doc(hidden)]
+pub fn record_or_end<S>(info: &RouterInfo, service: S) -> S {
+    info.events.borrow_mut().push(Event::OrEnd);
+
+    service
+}
+
+#[doc(hidden)]
+pub fn record_extension<T>(info: &RouterInfo, value: T) -> T {
+    info.events
+        .borrow_mut()
+        .push(Event::Extension(type_name::<T>()));
+
+    value
+}
+
+#[doc(hidden)]
+pub fn inspect<R>(router: R, info: RouterInfo) -> DebugRouter<R> {
+    DebugRouter { router, info }
+}
+
+/// Routes of a router in the order they were added.
+///
+/// Printed with [`Display`](fmt::Display) as a table, or as JSON with [`RouteTable::to_json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteTable {
+    routes: Vec<RouteInfo>,
+    // Indices of the routes in the order axum tries them.
+    order: Vec<usize>,
+    // Prefixes of nested routers with the number of routes in `order` tried before they end.
+    nests: Vec<(String, usize)>,
+}
+
+impl RouteTable {
+    /// Routes that shadow each other.
+    ///
+    /// axum tries the routes added last first, except that `a.or(b)` tries the routes of `a` before
+    /// those of `b`. A route can't be reached if a route tried before it serves the same method on
+    /// the same path, on a path matching the same requests, or if a nested router tried before it
+    /// has a prefix of its path.
+    pub fn conflicts(&self) -> Vec<RouteConflict> {
+        let mut vec = Vec::new();
+
+        // Handlers of a method chained last are tried first too.
+        let methods: Vec<(&RouteInfo, &MethodInfo)> = self
+            .order
+            .iter()
+            .rev()
+            .map(|route| &self.routes[*route])
+            .flat_map(|route| route.methods.iter().map(move |method| (route, method)))
+            .collect();
+
+        for (i, (first, first_method)) in methods.iter().enumerate() {
+            for (last, last_method) in &methods[i + 1..] {
+                let method = match overlapping_methods(first_method.method, last_method.method) {
+                    Some(method) => method,
+                    None => continue,
+                };
+
+                if first.path == last.path {
+                    vec.push(RouteConflict::Duplicate {
+                        path: first.path.clone(),
+                        method,
+                        first: first_method.handler,
+                        last: last_method.handler,
+                    });
+                } else if paths_overlap(&first.path, &last.path) {
+                    vec.push(RouteConflict::Overlap {
+                        first: first.path.clone(),
+                        last: last.path.clone(),
+                        method,
+                    });
+                }
+            }
+        }
+
+        for (prefix, end) in &self.nests {
+            for route in self.order[*end..]
+                .iter()
+                .rev()
+                .map(|route| &self.routes[*route])
+            {
+                if prefix_matches(prefix, &route.path) {
+                    vec.push(RouteConflict::Nest {
+                        prefix: prefix.clone(),
+                        path: route.path.clone(),
+                    });
+                }
+            }
+        }
+
+        vec
+    }
+
+    /// Iterate over the routes.
+    pub fn iter(&self) -> impl Iterator<Item = &RouteInfo> {
+        self.routes.iter()
+    }
+
+    /// Route serving requests to `path`, the one tried first if more than one matches.
+    pub fn find(&self, path: &str) -> Option<&RouteInfo> {
+        self.order
+            .iter()
+            .map(|route| &self.routes[*route])
+            .find(|route| path_matches(&route.path, path))
+    }
+
+    /// Formats the routes as a JSON array.
+    pub fn to_json(&self) -> String {
+        let routes: Vec<String> = self
+            .routes
+            .iter()
+            .map(|route| {
+                let methods: Vec<String> = route
+                    .methods
+                    .iter()
+                    .map(|method| {
+                        format!(
+                            "{{\"method\":{},\"handler\":{}}}",
+                            json_string(method.method),
+                            json_string(method.handler)
+                        )
+                    })
+                    .collect();
+
+                format!(
+                    "{{\"path\":{},\"prefix\":{},\"methods\":[{}]}}",
+                    json_string(&route.path),
+                    json_string(&route.prefix),
+                    methods.join(",")
+                )
+            })
+            .collect();
+
+        format!("[{}]", routes.join(","))
+    }
+}
+
+impl fmt::Display for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+
+        for route in &self.routes {
+            for method in &route.methods {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+
+                write!(
+                    f,
+                    "{:<7} {} -> {}",
+                    method.method, route.path, method.handler
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Route of a router.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    path: String,
+    prefix: String,
+    methods: Vec<MethodInfo>,
+}
+
+impl RouteInfo {
+    /// Full path of the route, including the prefix it is nested in.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Prefix the route is nested in, empty if it isn't nested.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Methods served by the route.
+    pub fn methods(&self) -> &[MethodInfo] {
+        &self.methods
+    }
+}
+
+/// Method served by a route and the handler serving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodInfo {
+    method: &'static str,
+    handler: &'static str,
+}
+
+impl MethodInfo {
+    /// Name of the method, `ANY` for handlers of every method.
+    ///
+    /// Methods given to `on` are joined with `|`, like `GET|POST`.
+    pub fn method(&self) -> &str {
+        self.method
+    }
+
+    /// Name of the handler or the type of the service.
+    pub fn handler(&self) -> &str {
+        self.handler
+    }
+}
+
+/// Route that can't be reached because of a route tried before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RouteConflict {
+    /// The same method of the same path is served twice.
+    Duplicate {
+        /// Path of the routes.
+        path: String,
+        /// Method served twice.
+        method: String,
+        /// Handler tried last, which isn't used.
+        first: &'static str,
+        /// Handler tried first.
+        last: &'static str,
+    },
+    /// Different paths match some of the same requests, like `/users/:id` and `/users/me`.
+    Overlap {
+        /// Path tried last, which is shadowed where the paths overlap.
+        first: String,
+        /// Path tried first.
+        last: String,
+        /// Method both paths serve.
+        method: String,
+    },
+    /// A router nested before a route is tried has a prefix of the route's path.
+    Nest {
+        /// Prefix of the nested router.
+        prefix: String,
+        /// Path of the shadowed route.
+        path: String,
+    },
+}
+
+impl fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteConflict::Duplicate {
+                path,
+                method,
+                first,
+                last,
+            } => write!(
+                f,
+                "`{} {}` is served by `{}` and `{}`, only `{}` is used",
+                method, path, first, last, last
+            ),
+            RouteConflict::Overlap {
+                first,
+                last,
+                method,
+            } => write!(
+                f,
+                "`{} {}` overlaps `{} {}`, requests matching both go to `{}`",
+                method, last, method, first, last
+            ),
+            RouteConflict::Nest { prefix, path } => {
+                write!(f, "router nested at `{}` shadows `{}`", prefix, path)
+            }
+        }
+    }
+}
+
+/// Methods served by both method filters, `None` if there aren't any.
+fn overlapping_methods(a: &str, b: &str) -> Option<String> {
+    match (a, b) {
+        ("ANY", "ANY") => Some("ANY".to_owned()),
+        ("ANY", other) | (other, "ANY") => Some(other.to_owned()),
+        _ => {
+            let b: Vec<&str> = b.split('|').collect();
+            let both: Vec<&str> = a.split('|').filter(|method| b.contains(method)).collect();
+
+            if both.is_empty() {
+                None
+            } else {
+                Some(both.join("|"))
+            }
+        }
+    }
+}
+
+/// Whether some request paths match both route paths.
+fn paths_overlap(a: &str, b: &str) -> bool {
+    let a: Vec<&str> = a.split('/').collect();
+    let b: Vec<&str> = b.split('/').collect();
+
+    let mut i = 0;
+
+    loop {
+        match (a.get(i), b.get(i)) {
+            (None, None) => return true,
+            (Some(a), Some(_)) | (Some(_), Some(a)) if a.starts_with('*') => return true,
+            (Some(a), Some(b)) if segments_overlap(a, b) => i += 1,
+            _ => return false,
+        }
+    }
+}
+
+/// Whether the request path `path` matches the route path `route`.
+fn path_matches(route: &str, path: &str) -> bool {
+    let route: Vec<&str> = route.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+
+    for (i, segment) in route.iter().enumerate() {
+        if segment.starts_with('*') {
+            return path.len() > i;
+        }
+
+        match path.get(i) {
+            Some(part) if part == segment || (segment.starts_with(':') && !part.is_empty()) => {}
+            _ => return false,
+        }
+    }
+
+    route.len() == path.len()
+}
+
+/// Whether every request path matching `path` starts with `prefix`.
+fn prefix_matches(prefix: &str, path: &str) -> bool {
+    let prefix: Vec<&str> = prefix.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+
+    prefix.len() <= path.len()
+        && prefix
+            .iter()
+            .zip(&path)
+            .all(|(prefix, segment)| segments_overlap(prefix, segment))
+}
+
+fn segments_overlap(a: &str, b: &str) -> bool {
+    a == b || (a.starts_with(':') && !b.is_empty()) || (b.starts_with(':') && !a.is_empty())
+}
+
+fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+
+    if path == "/" && !prefix.is_empty() {
+        prefix.to_owned()
+    } else {
+        format!("{}{}", prefix, path)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+/// Types in `Extension<T>` extractors within the extractor type `ty`. Optional extractors like
+/// `Option<Extension<T>>` don't require anything.
+fn required_extensions(ty: &str) -> Vec<&str> {
+    let ty = ty.trim();
+
+    if let Some(inner) = ty.strip_prefix('(').and_then(|ty| ty.strip_suffix(')')) {
+        return split_top_level(inner)
+            .into_iter()
+            .flat_map(required_extensions)
+            .collect();
+    }
+
+    match ty.find('<') {
+        Some(i) if ty.ends_with('>') => {
+            let outer = &ty[..i];
+
+            if outer.starts_with("axum::") && outer.ends_with("::Extension") {
+                vec![&ty[i + 1..ty.len() - 1]]
+            } else {
+                Vec::new()
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Splits a comma separated list of types, ignoring commas inside generics and tuples.
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut vec = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in list.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                vec.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if !list[start..].trim().is_empty() {
+        vec.push(&list[start..]);
+    }
+
+    vec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_extensions_of_extractors() {
+        assert_eq!(
+            required_extensions("axum::extract::extension::Extension<alloc::sync::Arc<app::Db>>"),
+            vec!["alloc::sync::Arc<app::Db>"]
+        );
+        assert_eq!(
+            required_extensions(
+                "(axum::extract::Extension<u32>, axum::extract::Extension<(u8, u16)>)"
+            ),
+            vec!["u32", "(u8, u16)"]
+        );
+        assert!(
+            required_extensions("core::option::Option<axum::extract::Extension<u32>>").is_empty()
+        );
+        assert!(required_extensions("alloc::string::String").is_empty());
+    }
+
+    #[test]
+    fn extensions_only_apply_to_earlier_routes() {
+        let info = RouterInfo::default();
+        record_extension(&info, 0u8);
+        info.events.borrow_mut().push(Event::Handler {
+            method: "GET",
+            name: "handler",
+            extractors: vec![type_name::<axum::extract::Extension<u32>>()],
+        });
+        record_extension(&info, 0u32);
+        info.events.borrow_mut().push(Event::Handler {
+            method: "GET",
+            name: "late",
+            extractors: vec![type_name::<axum::extract::Extension<u32>>()],
+        });
+
+        let missing = info.missing_extensions();
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].handler(), "late");
+        assert_eq!(missing[0].extension(), "u32");
+    }
+
+    #[test]
+    fn extensions_of_nested_routers() {
+        let handler = |info: &RouterInfo, name| {
+            info.events.borrow_mut().push(Event::Handler {
+                method: "GET",
+                name,
+                extractors: vec![type_name::<axum::extract::Extension<u32>>()],
+            });
+        };
+
+        let info = RouterInfo::default();
+        handler(&info, "outer");
+        record_nest(&info, "/a");
+        handler(&info, "nested");
+        record_extension(&info, 0u32);
+        record_nest_end(&info, ());
+        record_nest(&info, "/b");
+        record_extension(&info, 0u32);
+        record_nest_end(&info, ());
+
+        let missing = info.missing_extensions();
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].handler(), "outer");
+
+        let info = RouterInfo::default();
+        record_nest(&info, "/a");
+        handler(&info, "nested");
+        record_nest_end(&info, ());
+        record_extension(&info, 0u32);
+
+        assert!(info.missing_extensions().is_empty());
+    }
+
+    #[test]
+    fn nested_routes() {
+        let info = RouterInfo::default();
+        record_route(&info, "/");
+        record_service(&info, "GET", ());
+        record_nest(&info, "/api/");
+        record_route(&info, "/users");
+        record_service(&info, "GET", ());
+        record_service(&info, "POST", 0u8);
+        record_nest_end(&info, ());
+        record_nest(&info, "/static");
+        record_service(&info, "ANY", ());
+        record_nest_end(&info, ());
+
+        let routes = info.routes();
+
+        assert_eq!(
+            routes.to_string(),
+            "GET     / -> ()\n\
+             GET     /api/users -> ()\n\
+             POST    /api/users -> u8\n\
+             ANY     /static -> ()"
+        );
+        assert_eq!(routes.iter().nth(1).unwrap().prefix(), "/api");
+        assert_eq!(
+            routes.to_json(),
+            concat!(
+                r#"[{"path":"/","prefix":"","methods":[{"method":"GET","handler":"()"}]},"#,
+                r#"{"path":"/api/users","prefix":"/api","methods":[{"method":"GET","handler":"()"},"#,
+                r#"{"method":"POST","handler":"u8"}]},"#,
+                r#"{"path":"/static","prefix":"/static","methods":[{"method":"ANY","handler":"()"}]}]"#,
+            )
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let info = RouterInfo::default();
+        record_route(&info, "/users/me");
+        record_service(&info, "GET", 0u8);
+        record_route(&info, "/users/:id");
+        record_service(&info, "GET|POST", 0u16);
+        record_route(&info, "/users/:id");
+        record_service(&info, "POST", 0u32);
+        record_service(&info, "DELETE", ());
+        record_route(&info, "/api/items");
+        record_service(&info, "GET", ());
+        record_nest(&info, "/api");
+        record_route(&info, "/other");
+        record_service(&info, "GET", ());
+        record_nest_end(&info, ());
+
+        let conflicts: Vec<String> = info
+            .routes()
+            .conflicts()
+            .iter()
+            .map(|conflict| conflict.to_string())
+            .collect();
+
+        assert_eq!(
+            conflicts,
+            vec![
+                "`GET /users/:id` overlaps `GET /users/me`, requests matching both go to `/users/:id`",
+                "`POST /users/:id` is served by `u16` and `u32`, only `u32` is used",
+                "router nested at `/api` shadows `/api/items`",
+            ]
+        );
+    }
+
+    #[test]
+    fn or_precedence() {
+        let info = RouterInfo::default();
+        record_or(&info);
+        record_route(&info, "/users/:id");
+        record_service(&info, "GET", 0u8);
+        record_nest(&info, "/api");
+        record_route(&info, "/other");
+        record_service(&info, "GET", ());
+        record_nest_end(&info, ());
+        record_or_else(&info);
+        record_route(&info, "/users/me");
+        record_service(&info, "GET", 0u16);
+        record_route(&info, "/api/items");
+        record_service(&info, "GET", ());
+        record_or_end(&info, ());
+        record_route(&info, "/users/:id");
+        record_service(&info, "POST", 0u32);
+
+        let routes = info.routes();
+        let conflicts: Vec<String> = routes
+            .conflicts()
+            .iter()
+            .map(|conflict| conflict.to_string())
+            .collect();
+
+        assert_eq!(
+            conflicts,
+            vec![
+                "`GET /users/:id` overlaps `GET /users/me`, requests matching both go to `/users/:id`",
+                "router nested at `/api` shadows `/api/items`",
+            ]
+        );
+        assert_eq!(
+            routes.find("/users/me").unwrap().methods()[0].handler(),
+            "u32"
+        );
+
+        let info = RouterInfo::default();
+        record_or(&info);
+        record_route(&info, "/users/:id");
+        record_service(&info, "GET", 0u8);
+        record_or_else(&info);
+        record_route(&info, "/users/me");
+        record_service(&info, "GET", 0u16);
+        record_or_end(&info, ());
+
+        assert_eq!(
+            info.routes().find("/users/me").unwrap().methods()[0].handler(),
+            "u8"
+        );
+    }
+
+    #[test]
+    fn extensions_of_or() {
+        let handler = |info: &RouterInfo, name| {
+            info.events.borrow_mut().push(Event::Handler {
+                method: "GET",
+                name,
+                extractors: vec![type_name::<axum::extract::Extension<u32>>()],
+            });
+        };
+
+        let info = RouterInfo::default();
+        record_or(&info);
+        handler(&info, "first");
+        record_or_else(&info);
+        handler(&info, "second");
+        record_extension(&info, 0u32);
+        record_or_end(&info, ());
+
+        let missing = info.missing_extensions();
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].handler(), "first");
+
+        let info = RouterInfo::default();
+        record_or(&info);
+        handler(&info, "first");
+        record_or_else(&info);
+        handler(&info, "second");
+        record_or_end(&info, ());
+        record_extension(&info, 0u32);
+
+        assert!(info.missing_extensions().is_empty());
+    }
+
+    #[test]
+    fn paths() {
+        assert!(paths_overlap("/users/:id", "/users/me"));
+        assert!(paths_overlap("/files/*path", "/files/a/b"));
+        assert!(!paths_overlap("/users/:id", "/users"));
+        assert!(!paths_overlap("/users/:id", "/teams/:id"));
+        assert!(!paths_overlap("/files/*path", "/files"));
+
+        assert!(path_matches("/users/:id", "/users/42"));
+        assert!(path_matches("/files/*path", "/files/a/b"));
+        assert!(!path_matches("/users/:id", "/users/42/teams"));
+        assert!(!path_matches("/users/:id", "/users/"));
+    }
+}
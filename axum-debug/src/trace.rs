@@ -0,0 +1,68 @@
+//! Tracing handlers using `#[debug_handler(trace)]`.
+//!
+//! The `debug_` wrapper of a traced handler runs it inside a span named after the handler. Each of
+//! its extractors runs in a span named after the argument, like `Path(id): Path<u32>`, with these
+//! fields:
+//!
+//! - `argument`: position of the argument, starting from 1.
+//! - `extractor`: type of the extractor.
+//! - `outcome`: `ok`, or `rejected` if the extractor rejected the request.
+//! - `rejection`: type of the rejection, if there was one.
+//! - `elapsed`: time spent in the extractor.
+//!
+//! The handler itself runs in a span named `body`, with the `status` of its response and the
+//! `elapsed` time. It isn't run if an extractor rejects the request.
+//!
+//! Subscribers don't print spans by default. With `tracing-subscriber`, use
+//! `.with_span_events(FmtSpan::CLOSE)` to print every span along with its fields once it closes:
+//!
+//! ```text
+//! INFO create_user:Extension(db): Extension<Db>: close time.busy=12.4µs time.idle=3.1µs argument=1 extractor="axum::extract::Extension<app::Db>" outcome="ok" elapsed=14.2µs
+//! INFO create_user:Json(user): Json<User>: close time.busy=35.8µs time.idle=8.0µs argument=2 extractor="axum::extract::Json<app::User>" outcome="ok" elapsed=42.7µs
+//! INFO create_user:body: close time.busy=1.21ms time.idle=4.3µs status=201 elapsed=1.22ms
+//! INFO create_user: close time.busy=1.29ms time.idle=17.6µs
+//! ```
+
+use axum::{
+    body::{box_body, Body, BoxBody},
+    extract::{FromRequest, RequestParts},
+    response::IntoResponse,
+};
+use http::Response;
+use std::{any::type_name, future::Future, time::Instant};
+use tracing::{field::debug, Instrument, Span};
+
+#[doc(hidden)]
+pub async fn trace_extractor<T>(req: &mut RequestParts<Body>, span: Span) -> Result<T, T::Rejection>
+where
+    T: FromRequest<Body>,
+{
+    span.record("extractor", type_name::<T>());
+
+    let start = Instant::now();
+    let result = T::from_request(req).instrument(span.clone()).await;
+    span.record("elapsed", debug(start.elapsed()));
+
+    if result.is_ok() {
+        span.record("outcome", "ok");
+    } else {
+        span.record("outcome", "rejected");
+        span.record("rejection", type_name::<T::Rejection>());
+    }
+
+    result
+}
+
+#[doc(hidden)]
+pub async fn trace_handler<F>(future: F, span: Span) -> Response<BoxBody>
+where
+    F: Future,
+    F::Output: IntoResponse,
+{
+    let start = Instant::now();
+    let response = future.instrument(span.clone()).await.into_response();
+    span.record("elapsed", debug(start.elapsed()));
+    span.record("status", response.status().as_u16());
+
+    response.map(box_body)
+}